@@ -0,0 +1,274 @@
+use std::{collections::HashMap, sync::Arc};
+
+use chrono::{DateTime, Utc};
+use futures_util::{Stream, StreamExt};
+use serde::de::DeserializeOwned;
+use tokio::runtime::Runtime;
+
+use crate::{
+    embed::{
+        AlternateEpisodeEmbed, AlternateListEmbed, CreditEmbed, CrewCreditEmbed, EpisodeEmbed,
+        PersonEmbed, SeasonEmbed, ShowEmbed,
+    },
+    models::{
+        Aka, AlternateEpisode, AlternateList, CastCredit, CastMember, CrewCredit, CrewMember,
+        Episode, EpisodeGuests, FilmographyEntry, Link, Network, Person, PersonId,
+        ScheduledEpisode, SearchResult, SearchResultNetwork, SearchResultPerson,
+        SearchResultWebChannel, Season, Show, ShowId, ShowImage, WebChannel,
+    },
+    ExternId, Result, ScheduleQuery, TvMazeClient, TvMazeClientBuilder, UpdateWindow,
+    WebScheduleQuery,
+};
+
+/// Synchronous counterpart of [`TvMazeClient`], driving the async client on its own runtime. Must not be used from within an async context.
+#[derive(Clone, Debug)]
+pub struct BlockingTvMazeClient {
+    inner: TvMazeClient,
+    runtime: Arc<Runtime>,
+}
+
+impl BlockingTvMazeClient {
+    /// Create a client with the default configuration, pointed at the public TVmaze API.
+    pub fn new() -> Self {
+        Self::from_client(TvMazeClient::new()).expect("failed to start runtime")
+    }
+
+    /// Wrap an already configured async client.
+    pub fn from_client(inner: TvMazeClient) -> Result<Self> {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()?;
+
+        Ok(Self {
+            inner,
+            runtime: Arc::new(runtime),
+        })
+    }
+
+    /// The async client requests are made with.
+    pub fn client(&self) -> &TvMazeClient {
+        &self.inner
+    }
+
+    /// Blocking version of [`TvMazeClient::show_index_stream`], yielding the shows as an iterator. Pages are only requested while the iterator is advanced.
+    pub fn show_index_stream(
+        &self,
+        start: Option<ShowId>,
+    ) -> impl Iterator<Item = Result<Show>> + '_ {
+        self.iter(self.inner.show_index_stream(start))
+    }
+
+    /// Blocking version of [`TvMazeClient::person_index_stream`], yielding the people as an iterator. Pages are only requested while the iterator is advanced.
+    pub fn person_index_stream(
+        &self,
+        start: Option<PersonId>,
+    ) -> impl Iterator<Item = Result<Person>> + '_ {
+        self.iter(self.inner.person_index_stream(start))
+    }
+
+    /// Drive `stream` on the runtime one item at a time.
+    fn iter<'a, T>(&'a self, stream: impl Stream<Item = T> + 'a) -> impl Iterator<Item = T> + 'a {
+        let mut stream = Box::pin(stream);
+        std::iter::from_fn(move || self.runtime.block_on(stream.next()))
+    }
+}
+
+impl<T: DeserializeOwned> Link<T> {
+    /// Blocking version of [`Link::fetch`].
+    pub fn fetch_blocking(&self, client: &BlockingTvMazeClient) -> Result<Option<T>> {
+        client.runtime.block_on(self.fetch(&client.inner))
+    }
+}
+
+impl Default for BlockingTvMazeClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TvMazeClientBuilder {
+    pub fn build_blocking(self) -> Result<BlockingTvMazeClient> {
+        BlockingTvMazeClient::from_client(self.build()?)
+    }
+}
+
+macro_rules! blocking_methods {
+    ($(fn $name:ident$(<$generic:ident: $bound:ident $(+ $more:ident)*>)?(&self $(, $arg:ident: $ty:ty)*) -> $ret:ty;)*) => {
+        impl BlockingTvMazeClient {
+            $(
+                #[doc = concat!("Blocking version of [`TvMazeClient::", stringify!($name), "`](crate::TvMazeClient::", stringify!($name), ").")]
+                pub fn $name$(<$generic: $bound $(+ $more)*>)?(&self $(, $arg: $ty)*) -> Result<$ret> {
+                    self.runtime.block_on(self.inner.$name($($arg),*))
+                }
+            )*
+        }
+    };
+}
+
+blocking_methods! {
+    fn show<E: ShowEmbed>(&self, id: usize, embed: E) -> Option<Show<E>>;
+    fn show_episode_list(&self, show: usize, specials: bool) -> Option<Vec<Episode>>;
+    fn show_alternate_lists(&self, show: usize) -> Option<Vec<AlternateList>>;
+    fn alternate_list<E: AlternateListEmbed>(&self, id: usize, embed: E) -> Option<AlternateList<E>>;
+    fn alternate_episodes<E: AlternateEpisodeEmbed>(&self, list: usize, embed: E) -> Option<Vec<AlternateEpisode<E>>>;
+    fn episode_by_number(&self, show: usize, season: usize, episode: usize) -> Option<Episode>;
+    fn episodes_by_date(&self, show: usize, date: &str) -> Option<Vec<Episode>>;
+    fn show_seasons<E: SeasonEmbed>(&self, show: usize, embed: E) -> Option<Vec<Season<E>>>;
+    fn season<E: SeasonEmbed>(&self, id: usize, embed: E) -> Option<Season<E>>;
+    fn season_episodes(&self, season: usize) -> Option<Vec<Episode>>;
+    fn show_cast(&self, show: usize) -> Option<Vec<CastMember>>;
+    fn show_crew(&self, show: usize) -> Option<Vec<CrewMember>>;
+    fn show_akas(&self, show: usize) -> Option<Vec<Aka>>;
+    fn show_images(&self, show: usize) -> Option<Vec<ShowImage>>;
+    fn show_index(&self, page: usize) -> Option<Vec<Show>>;
+
+    fn show_search(&self, query: &str) -> Vec<SearchResult>;
+    fn show_single_search<E: ShowEmbed>(&self, query: &str, embed: E) -> Option<Show<E>>;
+    fn show_lookup(&self, extern_id: ExternId) -> Option<Show>;
+    fn people_search(&self, query: &str) -> Vec<SearchResultPerson>;
+    fn network_search(&self, query: &str) -> Vec<SearchResultNetwork>;
+    fn web_channel_search(&self, query: &str) -> Vec<SearchResultWebChannel>;
+
+    fn network(&self, id: usize) -> Option<Network>;
+    fn web_channel(&self, id: usize) -> Option<WebChannel>;
+    fn network_shows(&self, network: usize) -> Vec<Show>;
+    fn web_channel_shows(&self, web_channel: usize) -> Vec<Show>;
+
+    fn episode<E: EpisodeEmbed>(&self, id: usize, embed: E) -> Option<Episode<E>>;
+    fn episode_guest_cast(&self, id: usize) -> Option<Vec<CastMember>>;
+    fn episode_guest_crew(&self, id: usize) -> Option<Vec<CrewMember>>;
+    fn show_guests(&self, show: usize, specials: bool) -> Option<Vec<EpisodeGuests>>;
+
+    fn person<E: PersonEmbed>(&self, id: usize, embed: E) -> Option<Person<E>>;
+    fn person_cast_credits<E: CreditEmbed>(&self, id: usize, embed: E) -> Option<Vec<CastCredit<E>>>;
+    fn person_crew_credits<E: CrewCreditEmbed>(&self, id: usize, embed: E) -> Option<Vec<CrewCredit<E>>>;
+    fn person_filmography(&self, id: usize) -> Option<Vec<FilmographyEntry>>;
+    fn person_index(&self, page: usize) -> Option<Vec<Person>>;
+
+    fn schedule(&self, query: &ScheduleQuery) -> Vec<ScheduledEpisode>;
+    fn web_schedule(&self, query: &WebScheduleQuery) -> Vec<ScheduledEpisode>;
+    fn full_schedule(&self) -> Vec<ScheduledEpisode>;
+
+    fn show_updates(&self, window: UpdateWindow) -> HashMap<ShowId, DateTime<Utc>>;
+    fn person_updates(&self, window: UpdateWindow) -> HashMap<PersonId, DateTime<Utc>>;
+
+    fn evict_updated(&self) -> Vec<String>;
+    fn refresh_updated(&self) -> Vec<String>;
+}
+
+#[cfg(feature = "user-api")]
+mod user {
+    use chrono::{DateTime, Utc};
+
+    use super::BlockingTvMazeClient;
+    use crate::{
+        embed::{
+            EpisodeVoteEmbed, FollowedNetworkEmbed, FollowedPersonEmbed, FollowedShowEmbed,
+            FollowedWebChannelEmbed, MarkingEmbed, ShowEmbed, ShowVoteEmbed,
+        },
+        models::{
+            EpisodeId, EpisodeMarking, EpisodeVote, FollowedNetwork, FollowedPerson, FollowedShow,
+            FollowedWebChannel, MarkType, NetworkId, PersonId, Show, ShowId, ShowVote, Tag, TagId,
+            TaggedShow, WebChannelId,
+        },
+        Result,
+    };
+
+    blocking_methods! {
+        fn followed_shows<E: FollowedShowEmbed>(&self, embed: E) -> Vec<FollowedShow<E>>;
+        fn followed_show<E: FollowedShowEmbed>(&self, show: ShowId, embed: E) -> Option<FollowedShow<E>>;
+        fn follow_show(&self, show: ShowId) -> FollowedShow;
+        fn unfollow_show(&self, show: ShowId) -> bool;
+
+        fn followed_people<E: FollowedPersonEmbed>(&self, embed: E) -> Vec<FollowedPerson<E>>;
+        fn followed_person<E: FollowedPersonEmbed>(&self, person: PersonId, embed: E) -> Option<FollowedPerson<E>>;
+        fn follow_person(&self, person: PersonId) -> FollowedPerson;
+        fn unfollow_person(&self, person: PersonId) -> bool;
+
+        fn followed_networks<E: FollowedNetworkEmbed>(&self, embed: E) -> Vec<FollowedNetwork<E>>;
+        fn followed_network<E: FollowedNetworkEmbed>(&self, network: NetworkId, embed: E) -> Option<FollowedNetwork<E>>;
+        fn follow_network(&self, network: NetworkId) -> FollowedNetwork;
+        fn unfollow_network(&self, network: NetworkId) -> bool;
+
+        fn followed_web_channels<E: FollowedWebChannelEmbed>(&self, embed: E) -> Vec<FollowedWebChannel<E>>;
+        fn followed_web_channel<E: FollowedWebChannelEmbed>(&self, web_channel: WebChannelId, embed: E) -> Option<FollowedWebChannel<E>>;
+        fn follow_web_channel(&self, web_channel: WebChannelId) -> FollowedWebChannel;
+        fn unfollow_web_channel(&self, web_channel: WebChannelId) -> bool;
+
+        fn episode_markings<E: MarkingEmbed>(&self, show: Option<ShowId>, embed: E) -> Vec<EpisodeMarking<E>>;
+        fn episode_marking<E: MarkingEmbed>(&self, episode: EpisodeId, embed: E) -> Option<EpisodeMarking<E>>;
+        fn mark_episode(&self, episode: EpisodeId, mark_type: MarkType, marked_at: DateTime<Utc>) -> EpisodeMarking;
+        fn unmark_episode(&self, episode: EpisodeId) -> bool;
+        fn mark_season(&self, season: usize, mark_type: MarkType, marked_at: DateTime<Utc>) -> Option<Vec<EpisodeMarking>>;
+
+        fn show_votes<E: ShowVoteEmbed>(&self, embed: E) -> Vec<ShowVote<E>>;
+        fn show_vote<E: ShowVoteEmbed>(&self, show: ShowId, embed: E) -> Option<ShowVote<E>>;
+        fn vote_show(&self, show: ShowId, vote: u8) -> ShowVote;
+        fn unvote_show(&self, show: ShowId) -> bool;
+
+        fn episode_votes<E: EpisodeVoteEmbed>(&self, embed: E) -> Vec<EpisodeVote<E>>;
+        fn episode_vote<E: EpisodeVoteEmbed>(&self, episode: EpisodeId, embed: E) -> Option<EpisodeVote<E>>;
+        fn vote_episode(&self, episode: EpisodeId, vote: u8) -> EpisodeVote;
+        fn unvote_episode(&self, episode: EpisodeId) -> bool;
+
+        fn tags(&self) -> Vec<Tag>;
+        fn create_tag(&self, name: &str) -> Tag;
+        fn rename_tag(&self, tag: TagId, name: &str) -> Tag;
+        fn delete_tag(&self, tag: TagId) -> bool;
+        fn tag_shows(&self, tag: TagId) -> Option<Vec<TaggedShow>>;
+        fn tagged_shows<E: ShowEmbed + Clone>(&self, tag: TagId, embed: E) -> Option<Vec<Show<E>>>;
+        fn tag_show(&self, tag: TagId, show: ShowId) -> TaggedShow;
+        fn untag_show(&self, tag: TagId, show: ShowId) -> bool;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::BlockingTvMazeClient;
+    use crate::{
+        models::ShowId,
+        tests::{fixture_client, index_page, memory_client, FULL_EMBED, TEST_SHOWS},
+        transport::MemoryTransport,
+    };
+
+    #[test]
+    fn test_blocking_show() {
+        let client = BlockingTvMazeClient::from_client(fixture_client()).unwrap();
+        for (name, id, _) in TEST_SHOWS {
+            assert_eq!(client.show(id, FULL_EMBED).unwrap().unwrap().name, name);
+        }
+    }
+
+    #[test]
+    fn test_blocking_episode_by_number() {
+        let client = BlockingTvMazeClient::from_client(fixture_client()).unwrap();
+        assert_eq!(
+            client.episode_by_number(433, 1, 18).unwrap().unwrap().name,
+            "Apotheosis"
+        );
+    }
+
+    #[test]
+    fn test_blocking_follow_link() {
+        let client = BlockingTvMazeClient::from_client(fixture_client()).unwrap();
+        let show = client.show(1, ()).unwrap().unwrap();
+        let again = show._links._self.fetch_blocking(&client).unwrap().unwrap();
+        assert_eq!(again.id, show.id);
+    }
+
+    #[test]
+    fn test_blocking_show_index_stream() {
+        let transport = MemoryTransport::new()
+            .with_json("/shows?page=0", index_page("/shows/1", [1, 2]))
+            .with_json("/shows?page=1", index_page("/shows/1", [250]));
+        let client = BlockingTvMazeClient::from_client(memory_client(Arc::new(transport))).unwrap();
+
+        let ids: Vec<_> = client
+            .show_index_stream(Some(ShowId(2)))
+            .map(|show| show.unwrap().id)
+            .collect();
+        assert_eq!(ids, [2, 250]);
+    }
+}
@@ -0,0 +1,120 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+
+use super::{Cache, CacheEntry};
+
+/// Cache storing one JSON file per entry in a directory, surviving restarts. I/O errors are treated as cache misses.
+#[derive(Debug)]
+pub struct FsCache {
+    dir: PathBuf,
+}
+
+#[derive(Serialize, Deserialize)]
+struct StoredEntry {
+    key: String,
+    entry: CacheEntry,
+}
+
+impl FsCache {
+    /// Store entries in `dir`, creating it if needed.
+    pub fn new(dir: impl Into<PathBuf>) -> std::io::Result<Self> {
+        let dir = dir.into();
+        fs::create_dir_all(&dir)?;
+        Ok(Self { dir })
+    }
+
+    fn path(&self, key: &str) -> PathBuf {
+        self.dir.join(format!("{:016x}.json", fnv1a(key)))
+    }
+
+    fn read(path: &Path) -> Option<StoredEntry> {
+        serde_json::from_slice(&fs::read(path).ok()?).ok()
+    }
+}
+
+impl Cache for FsCache {
+    fn get(&self, key: &str) -> Option<CacheEntry> {
+        Self::read(&self.path(key))
+            .filter(|stored| stored.key == key)
+            .map(|stored| stored.entry)
+    }
+
+    fn put(&self, key: &str, entry: CacheEntry) {
+        let stored = StoredEntry {
+            key: key.to_string(),
+            entry,
+        };
+        if let Ok(content) = serde_json::to_vec(&stored) {
+            // Write to a temporary file first, so readers never see a partial entry.
+            let path = self.path(key);
+            let tmp = path.with_extension("tmp");
+            if fs::write(&tmp, content).is_ok() {
+                let _ = fs::rename(tmp, path);
+            }
+        }
+    }
+
+    fn remove(&self, key: &str) {
+        let _ = fs::remove_file(self.path(key));
+    }
+
+    fn keys(&self) -> Vec<String> {
+        let Ok(dir) = fs::read_dir(&self.dir) else {
+            return Vec::new();
+        };
+
+        dir.filter_map(|file| {
+            let path = file.ok()?.path();
+            if path.extension()? != "json" {
+                return None;
+            }
+            Some(Self::read(&path)?.key)
+        })
+        .collect()
+    }
+}
+
+/// FNV-1a, used for file names since it is stable across Rust versions.
+fn fnv1a(s: &str) -> u64 {
+    s.bytes().fold(0xcbf29ce484222325, |hash, b| {
+        (hash ^ b as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::FsCache;
+    use crate::cache::{Cache, CacheEntry};
+
+    #[test]
+    fn test_fs_cache() {
+        let dir = std::env::temp_dir().join(format!("tvmaze-fs-cache-{}", std::process::id()));
+        let cache = FsCache::new(&dir).unwrap();
+
+        cache.put(
+            "https://api.tvmaze.com/shows/1",
+            CacheEntry {
+                body: "{}".to_string(),
+                etag: Some("\"v1\"".to_string()),
+                last_modified: None,
+                stored_at: 1,
+                expires_at: 2,
+            },
+        );
+
+        let reopened = FsCache::new(&dir).unwrap();
+        let entry = reopened.get("https://api.tvmaze.com/shows/1").unwrap();
+        assert_eq!(entry.body, "{}");
+        assert_eq!(entry.etag.as_deref(), Some("\"v1\""));
+        assert_eq!(reopened.keys(), vec!["https://api.tvmaze.com/shows/1"]);
+
+        reopened.remove("https://api.tvmaze.com/shows/1");
+        assert!(cache.get("https://api.tvmaze.com/shows/1").is_none());
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,259 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+
+use crate::{
+    cache::unix_now,
+    models::{PersonId, ShowId},
+    people::PERSON_INDEX_PAGE_SIZE,
+    shows::SHOW_INDEX_PAGE_SIZE,
+    transport::path_and_query,
+    Result, TvMazeClient, UpdateWindow,
+};
+
+/// What a cached response is about, as far as the update endpoints can tell.
+#[derive(Debug, PartialEq, Eq)]
+enum Subject {
+    Show(ShowId),
+    Person(PersonId),
+    ShowIndexPage(usize),
+    PersonIndexPage(usize),
+}
+
+impl Subject {
+    fn from_path(path: &str) -> Option<Self> {
+        let (path, query) = path.split_once('?').unwrap_or((path, ""));
+        let mut segments = path.trim_start_matches('/').split('/');
+
+        let page = || {
+            query
+                .split('&')
+                .find_map(|param| param.strip_prefix("page="))
+                .and_then(|page| page.parse().ok())
+        };
+
+        match (segments.next()?, segments.next()) {
+            ("shows", Some(id)) => id.parse().ok().map(|id| Subject::Show(ShowId(id))),
+            ("people", Some(id)) => id.parse().ok().map(|id| Subject::Person(PersonId(id))),
+            ("shows", None) => page().map(Subject::ShowIndexPage),
+            ("people", None) => page().map(Subject::PersonIndexPage),
+            _ => None,
+        }
+    }
+
+    /// Whether the subject changed after `stored_at`.
+    fn updated_since(
+        &self,
+        stored_at: u64,
+        shows: &HashMap<ShowId, DateTime<Utc>>,
+        people: &HashMap<PersonId, DateTime<Utc>>,
+    ) -> bool {
+        let newer = |updated: &DateTime<Utc>| updated.timestamp() > stored_at as i64;
+        match self {
+            Subject::Show(id) => shows.get(id).is_some_and(newer),
+            Subject::Person(id) => people.get(id).is_some_and(newer),
+            Subject::ShowIndexPage(page) => {
+                let ids = page * SHOW_INDEX_PAGE_SIZE..(page + 1) * SHOW_INDEX_PAGE_SIZE;
+                ids.filter_map(|id| shows.get(&ShowId(id))).any(newer)
+            }
+            Subject::PersonIndexPage(page) => {
+                let ids = page * PERSON_INDEX_PAGE_SIZE..(page + 1) * PERSON_INDEX_PAGE_SIZE;
+                ids.filter_map(|id| people.get(&PersonId(id))).any(newer)
+            }
+        }
+    }
+}
+
+/// Smallest update window containing every update of the past `age` seconds.
+pub(crate) fn covering_window(age: u64) -> UpdateWindow {
+    const DAY: u64 = 24 * 60 * 60;
+
+    match age {
+        age if age < DAY => UpdateWindow::Day,
+        age if age < 7 * DAY => UpdateWindow::Week,
+        age if age < 30 * DAY => UpdateWindow::Month,
+        _ => UpdateWindow::All,
+    }
+}
+
+impl TvMazeClient {
+    /// The cached responses the update endpoints can tell about, with their subjects and when they were stored.
+    fn update_candidates(&self) -> Vec<(String, Subject, u64)> {
+        let Some(cache) = self.cache() else {
+            return Vec::new();
+        };
+
+        let backend = cache.backend();
+        backend
+            .keys()
+            .into_iter()
+            .filter_map(|key| {
+                let subject = Subject::from_path(&path_and_query(&key))?;
+                let entry = backend.get(&key)?;
+                Some((key, subject, entry.stored_at))
+            })
+            .collect()
+    }
+
+    /// When the oldest cached response the update endpoints can tell about was stored, or `None` if there is none.
+    pub(crate) fn oldest_update_candidate(&self) -> Option<u64> {
+        self.update_candidates()
+            .into_iter()
+            .map(|(_, _, stored_at)| stored_at)
+            .min()
+    }
+
+    /// Evict the cached responses whose subject was updated after they were stored according to already fetched updates, which must cover [`TvMazeClient::oldest_update_candidate`]. Returns the URLs of the evicted entries.
+    pub(crate) fn evict_with(
+        &self,
+        shows: &HashMap<ShowId, DateTime<Utc>>,
+        people: &HashMap<PersonId, DateTime<Utc>>,
+    ) -> Vec<String> {
+        let Some(cache) = self.cache() else {
+            return Vec::new();
+        };
+
+        let mut evicted = Vec::new();
+        for (key, subject, stored_at) in self.update_candidates() {
+            if subject.updated_since(stored_at, shows, people) {
+                cache.backend().remove(&key);
+                evicted.push(key);
+            }
+        }
+        evicted
+    }
+
+    /// Evict cached show and person responses whose subject was updated after the response was stored, according to the show and person update endpoints. This includes sub-resources like `/shows/:id/cast` and the show and person index pages containing an updated show or person. Call this periodically to keep a long-lived cache current without lowering TTLs; only updates since the oldest affected entry are downloaded. Returns the URLs of the evicted entries, and does nothing if caching is disabled.
+    pub async fn evict_updated(&self) -> Result<Vec<String>> {
+        let Some(oldest) = self.oldest_update_candidate() else {
+            return Ok(Vec::new());
+        };
+        let window = covering_window(unix_now().saturating_sub(oldest));
+
+        let shows = self.show_updates(window).await?;
+        let people = self.person_updates(window).await?;
+        Ok(self.evict_with(&shows, &people))
+    }
+
+    /// Like [`TvMazeClient::evict_updated`], but fetches the evicted responses again right away so the cache stays warm.
+    pub async fn refresh_updated(&self) -> Result<Vec<String>> {
+        let evicted = self.evict_updated().await?;
+        for key in &evicted {
+            self.fetch(&path_and_query(key)).await?;
+        }
+        Ok(evicted)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::{covering_window, Subject};
+    use crate::{
+        cache::{unix_now, Cache, CacheConfig, CacheEntry, MemoryCache},
+        models::{PersonId, ShowId},
+        transport::MemoryTransport,
+        TvMazeClient, UpdateWindow,
+    };
+
+    #[test]
+    fn test_subject_from_path() {
+        assert_eq!(
+            Subject::from_path("/shows/1"),
+            Some(Subject::Show(ShowId(1)))
+        );
+        assert_eq!(
+            Subject::from_path("/shows/1/cast?embed[]=x"),
+            Some(Subject::Show(ShowId(1)))
+        );
+        assert_eq!(
+            Subject::from_path("/people/7/castcredits"),
+            Some(Subject::Person(PersonId(7)))
+        );
+        assert_eq!(
+            Subject::from_path("/shows?page=2"),
+            Some(Subject::ShowIndexPage(2))
+        );
+        assert_eq!(
+            Subject::from_path("/people?page=3"),
+            Some(Subject::PersonIndexPage(3))
+        );
+        assert_eq!(Subject::from_path("/episodes/1"), None);
+        assert_eq!(Subject::from_path("/updates/shows"), None);
+    }
+
+    #[test]
+    fn test_covering_window() {
+        assert_eq!(covering_window(0), UpdateWindow::Day);
+        assert_eq!(covering_window(2 * 24 * 60 * 60), UpdateWindow::Week);
+        assert_eq!(covering_window(8 * 24 * 60 * 60), UpdateWindow::Month);
+        assert_eq!(covering_window(90 * 24 * 60 * 60), UpdateWindow::All);
+    }
+
+    #[tokio::test]
+    async fn test_evict_updated() {
+        let now = unix_now();
+        let transport = Arc::new(
+            MemoryTransport::new()
+                .with_json(
+                    "/updates/shows?since=day",
+                    format!(
+                        r#"{{"1": {}, "2": {}, "300": {}}}"#,
+                        now - 100,
+                        now + 100,
+                        now + 100
+                    ),
+                )
+                .with_json(
+                    "/updates/people?since=day",
+                    format!(r#"{{"1": {}}}"#, now + 100),
+                ),
+        );
+        let cache = Arc::new(MemoryCache::new(100));
+        let client = TvMazeClient::builder()
+            .transport(transport)
+            .rate_limit(None)
+            .cache(CacheConfig::new(cache.clone()))
+            .build()
+            .unwrap();
+
+        let entry = CacheEntry {
+            body: "[]".to_string(),
+            etag: None,
+            last_modified: None,
+            stored_at: now,
+            expires_at: now + 1000,
+        };
+        for path in [
+            "/shows/1",
+            "/shows/2/cast",
+            "/people/1",
+            "/shows?page=1",
+            "/shows?page=2",
+            "/people?page=0",
+            "/people?page=1",
+            "/episodes/5",
+        ] {
+            cache.put(&client.url(path), entry.clone());
+        }
+
+        let mut evicted = client.evict_updated().await.unwrap();
+        evicted.sort();
+
+        assert_eq!(
+            evicted,
+            [
+                "/people/1",
+                "/people?page=0",
+                "/shows/2/cast",
+                "/shows?page=1"
+            ]
+            .map(|path| client.url(path))
+        );
+        assert!(cache.get(&client.url("/shows/1")).is_some());
+        assert!(cache.get(&client.url("/shows?page=2")).is_some());
+        assert!(cache.get(&client.url("/people?page=1")).is_some());
+        assert!(cache.get(&client.url("/episodes/5")).is_some());
+    }
+}
@@ -0,0 +1,111 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::Mutex,
+};
+
+use super::{Cache, CacheEntry};
+
+/// In-memory cache evicting the least recently used entry once `capacity` entries are stored.
+#[derive(Debug)]
+pub struct MemoryCache {
+    capacity: usize,
+    state: Mutex<Lru>,
+}
+
+#[derive(Debug, Default)]
+struct Lru {
+    tick: u64,
+    entries: HashMap<String, (u64, CacheEntry)>,
+    order: BTreeMap<u64, String>,
+}
+
+impl Lru {
+    fn touch(&mut self, key: &str) {
+        self.tick += 1;
+        let tick = self.tick;
+        if let Some((used, _)) = self.entries.get_mut(key) {
+            self.order.remove(used);
+            *used = tick;
+            self.order.insert(tick, key.to_string());
+        }
+    }
+}
+
+impl MemoryCache {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            state: Default::default(),
+        }
+    }
+}
+
+impl Cache for MemoryCache {
+    fn get(&self, key: &str) -> Option<CacheEntry> {
+        let mut state = self.state.lock().unwrap();
+        state.touch(key);
+        state.entries.get(key).map(|(_, entry)| entry.clone())
+    }
+
+    fn put(&self, key: &str, entry: CacheEntry) {
+        let mut state = self.state.lock().unwrap();
+
+        if let Some((used, _)) = state.entries.remove(key) {
+            state.order.remove(&used);
+        }
+        while state.entries.len() >= self.capacity.max(1) {
+            let Some((_, oldest)) = state.order.pop_first() else {
+                break;
+            };
+            state.entries.remove(&oldest);
+        }
+
+        state.tick += 1;
+        let tick = state.tick;
+        state.entries.insert(key.to_string(), (tick, entry));
+        state.order.insert(tick, key.to_string());
+    }
+
+    fn remove(&self, key: &str) {
+        let mut state = self.state.lock().unwrap();
+        if let Some((used, _)) = state.entries.remove(key) {
+            state.order.remove(&used);
+        }
+    }
+
+    fn keys(&self) -> Vec<String> {
+        self.state.lock().unwrap().entries.keys().cloned().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MemoryCache;
+    use crate::cache::{Cache, CacheEntry};
+
+    fn entry(body: &str) -> CacheEntry {
+        CacheEntry {
+            body: body.to_string(),
+            etag: None,
+            last_modified: None,
+            stored_at: 0,
+            expires_at: 0,
+        }
+    }
+
+    #[test]
+    fn test_lru_eviction() {
+        let cache = MemoryCache::new(2);
+        cache.put("a", entry("a"));
+        cache.put("b", entry("b"));
+        cache.get("a");
+        cache.put("c", entry("c"));
+
+        assert!(cache.get("b").is_none());
+        assert_eq!(cache.get("a").unwrap().body, "a");
+        assert_eq!(cache.get("c").unwrap().body, "c");
+
+        cache.remove("a");
+        assert_eq!(cache.keys(), vec!["c".to_string()]);
+    }
+}
@@ -0,0 +1,310 @@
+use std::{
+    fmt::Debug,
+    sync::Arc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use reqwest::header::{HeaderMap, CACHE_CONTROL, ETAG, LAST_MODIFIED};
+use serde::{Deserialize, Serialize};
+
+mod fs;
+pub use fs::*;
+mod invalidate;
+pub(crate) use invalidate::covering_window;
+mod memory;
+pub use memory::*;
+
+/// A cached response body together with what is needed to revalidate it.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct CacheEntry {
+    pub body: String,
+    pub etag: Option<String>,
+    pub last_modified: Option<String>,
+    /// Unix timestamp of when the body was last fetched or revalidated.
+    pub stored_at: u64,
+    /// Unix timestamp after which the entry has to be revalidated.
+    pub expires_at: u64,
+}
+
+impl CacheEntry {
+    pub fn is_fresh(&self) -> bool {
+        unix_now() < self.expires_at
+    }
+}
+
+/// Storage backend for cached responses, keyed by request URL.
+pub trait Cache: Debug + Send + Sync {
+    fn get(&self, key: &str) -> Option<CacheEntry>;
+    fn put(&self, key: &str, entry: CacheEntry);
+    fn remove(&self, key: &str);
+    /// Keys of all stored entries, in no particular order.
+    fn keys(&self) -> Vec<String>;
+}
+
+/// Which responses to cache and for how long. Routes are written like in the TVmaze documentation, e.g. `/shows/:id/akas`, where every segment starting with `:` matches any value. The query string is not part of the route.
+#[derive(Clone, Debug)]
+pub struct CacheConfig {
+    backend: Arc<dyn Cache>,
+    ttls: Vec<(String, Duration)>,
+    default_ttl: Duration,
+}
+
+impl CacheConfig {
+    /// Cache in `backend` with TTLs suited to how often the TVmaze endpoints change.
+    pub fn new(backend: Arc<dyn Cache>) -> Self {
+        Self {
+            backend,
+            ttls: Vec::new(),
+            default_ttl: Duration::from_secs(60 * 60),
+        }
+        .ttl("/schedule", Duration::from_secs(10 * 60))
+        .ttl("/schedule/web", Duration::from_secs(10 * 60))
+        .ttl("/schedule/full", Duration::from_secs(24 * 60 * 60))
+        .ttl("/updates/shows", Duration::from_secs(5 * 60))
+        .ttl("/updates/people", Duration::from_secs(5 * 60))
+        .ttl("/shows", Duration::from_secs(24 * 60 * 60))
+        .ttl("/people", Duration::from_secs(24 * 60 * 60))
+        .ttl("/shows/:id/akas", Duration::from_secs(7 * 24 * 60 * 60))
+        .ttl("/shows/:id/images", Duration::from_secs(24 * 60 * 60))
+    }
+
+    /// Cache responses of `route` for `ttl`, or shorter if the server's `Cache-Control` asks for it, replacing an earlier TTL for the same route. A TTL of zero revalidates on every request.
+    pub fn ttl(mut self, route: &str, ttl: Duration) -> Self {
+        self.ttls.retain(|(r, _)| r != route);
+        self.ttls.push((route.to_string(), ttl));
+        self
+    }
+
+    /// TTL of routes without an explicit TTL and without a `Cache-Control: max-age` header.
+    pub fn default_ttl(mut self, ttl: Duration) -> Self {
+        self.default_ttl = ttl;
+        self
+    }
+
+    pub fn backend(&self) -> &Arc<dyn Cache> {
+        &self.backend
+    }
+
+    /// Build the entry to store for a successful response, or `None` if it must not be stored.
+    pub(crate) fn entry(
+        &self,
+        path: &str,
+        headers: &HeaderMap,
+        body: String,
+    ) -> Option<CacheEntry> {
+        let ttl = self.response_ttl(path, headers)?;
+        let now = unix_now();
+        Some(CacheEntry {
+            body,
+            etag: header_str(headers, ETAG.as_str()),
+            last_modified: header_str(headers, LAST_MODIFIED.as_str()),
+            stored_at: now,
+            expires_at: now + ttl,
+        })
+    }
+
+    /// Mark `entry` as revalidated now by a response with `headers`, returning `false` if it must not be stored anymore.
+    pub(crate) fn refresh(&self, path: &str, headers: &HeaderMap, entry: &mut CacheEntry) -> bool {
+        let Some(ttl) = self.response_ttl(path, headers) else {
+            return false;
+        };
+        entry.stored_at = unix_now();
+        entry.expires_at = entry.stored_at + ttl;
+        true
+    }
+
+    /// TTL in seconds of a response to `path`, or `None` if it must not be stored. The server's `no-cache` and `max-age` can shorten the TTL configured for the route, but never extend it.
+    fn response_ttl(&self, path: &str, headers: &HeaderMap) -> Option<u64> {
+        let directives = header_str(headers, CACHE_CONTROL.as_str()).unwrap_or_default();
+        let mut max_age = None;
+        for directive in directives.split(',').map(str::trim) {
+            match directive {
+                "no-store" => return None,
+                "no-cache" => max_age = Some(0),
+                _ => {
+                    if let Some(age) = directive.strip_prefix("max-age=") {
+                        max_age = max_age.or_else(|| age.parse().ok());
+                    }
+                }
+            }
+        }
+
+        Some(match (self.route_ttl(path), max_age) {
+            (Some(ttl), Some(max_age)) => ttl.as_secs().min(max_age),
+            (Some(ttl), None) => ttl.as_secs(),
+            (None, Some(max_age)) => max_age,
+            (None, None) => self.default_ttl.as_secs(),
+        })
+    }
+
+    fn route_ttl(&self, path: &str) -> Option<Duration> {
+        self.ttls
+            .iter()
+            .find(|(route, _)| route_matches(route, path))
+            .map(|(_, ttl)| *ttl)
+    }
+}
+
+fn route_matches(route: &str, path: &str) -> bool {
+    let path = path.split('?').next().unwrap_or_default();
+    let mut route = route.split('/');
+    let mut path = path.split('/');
+
+    loop {
+        match (route.next(), path.next()) {
+            (None, None) => return true,
+            (Some(r), Some(p)) if (r.starts_with(':') && !p.is_empty()) || r == p => {}
+            _ => return false,
+        }
+    }
+}
+
+fn header_str(headers: &HeaderMap, name: &str) -> Option<String> {
+    headers
+        .get(name)
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_string)
+}
+
+pub(crate) fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::Arc, time::Duration};
+
+    use reqwest::{
+        header::{HeaderMap, HeaderValue, CACHE_CONTROL, ETAG, IF_NONE_MATCH},
+        StatusCode,
+    };
+
+    use super::{route_matches, Cache, CacheConfig, MemoryCache};
+    use crate::{
+        transport::{MemoryTransport, Response},
+        TvMazeClient,
+    };
+
+    const AKAS: &str = r#"[{"name": "Aka", "country": null}]"#;
+
+    fn cached_client(transport: Arc<MemoryTransport>, config: CacheConfig) -> TvMazeClient {
+        TvMazeClient::builder()
+            .transport(transport)
+            .rate_limit(None)
+            .cache(config)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_route_matches() {
+        assert!(route_matches("/shows/:id/akas", "/shows/1/akas"));
+        assert!(route_matches("/shows", "/shows?page=3"));
+        assert!(!route_matches("/shows", "/shows/1"));
+        assert!(!route_matches("/shows/:id", "/shows/1/akas"));
+        assert!(!route_matches("/schedule", "/schedule/full"));
+    }
+
+    #[test]
+    fn test_entry_ttl() {
+        let config = CacheConfig::new(Arc::new(MemoryCache::new(10)))
+            .default_ttl(Duration::from_secs(100))
+            .ttl("/shows/:id", Duration::from_secs(5));
+
+        let mut headers = HeaderMap::new();
+        headers.insert(ETAG, HeaderValue::from_static("\"v1\""));
+        headers.insert(
+            CACHE_CONTROL,
+            HeaderValue::from_static("public, max-age=30"),
+        );
+
+        let entry = config.entry("/shows/1", &headers, String::new()).unwrap();
+        assert_eq!(entry.expires_at - entry.stored_at, 5);
+        assert_eq!(entry.etag.as_deref(), Some("\"v1\""));
+
+        // The server can shorten a route's TTL, but not extend it.
+        let entry = config
+            .entry("/shows/1/akas", &headers, String::new())
+            .unwrap();
+        assert_eq!(entry.expires_at - entry.stored_at, 30);
+        let mut no_cache = HeaderMap::new();
+        no_cache.insert(CACHE_CONTROL, HeaderValue::from_static("no-cache"));
+        let entry = config
+            .entry("/shows/1/akas", &no_cache, String::new())
+            .unwrap();
+        assert_eq!(entry.expires_at, entry.stored_at);
+
+        let entry = config
+            .entry("/episodes/1", &headers, String::new())
+            .unwrap();
+        assert_eq!(entry.expires_at - entry.stored_at, 30);
+
+        let entry = config
+            .entry("/episodes/1", &HeaderMap::new(), String::new())
+            .unwrap();
+        assert_eq!(entry.expires_at - entry.stored_at, 100);
+
+        headers.insert(CACHE_CONTROL, HeaderValue::from_static("no-store"));
+        assert!(config.entry("/shows/1", &headers, String::new()).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_fresh_hit() {
+        let transport = Arc::new(MemoryTransport::new().with_json("/shows/1/akas", AKAS));
+        let client = cached_client(
+            transport.clone(),
+            CacheConfig::new(Arc::new(MemoryCache::new(10))),
+        );
+
+        for _ in 0..3 {
+            assert_eq!(client.show_akas(1).await.unwrap().unwrap()[0].name, "Aka");
+        }
+        assert_eq!(transport.requests().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_revalidation() {
+        let mut first = Response::json(AKAS);
+        first
+            .headers
+            .insert(ETAG, HeaderValue::from_static("\"v1\""));
+        let transport = Arc::new(
+            MemoryTransport::new()
+                .with_response("/shows/1/akas", first)
+                .with_response("/shows/1/akas", Response::new(StatusCode::NOT_MODIFIED, "")),
+        );
+        let client = cached_client(
+            transport.clone(),
+            CacheConfig::new(Arc::new(MemoryCache::new(10))).ttl("/shows/:id/akas", Duration::ZERO),
+        );
+
+        client.show_akas(1).await.unwrap();
+        assert_eq!(client.show_akas(1).await.unwrap().unwrap()[0].name, "Aka");
+
+        let requests = transport.requests();
+        assert_eq!(requests.len(), 2);
+        assert!(requests[0].headers.get(IF_NONE_MATCH).is_none());
+        assert_eq!(requests[1].headers[IF_NONE_MATCH], "\"v1\"");
+    }
+
+    #[tokio::test]
+    async fn test_not_found_evicts() {
+        let transport = Arc::new(
+            MemoryTransport::new()
+                .with_json("/shows/1/akas", AKAS)
+                .with_response("/shows/1/akas", Response::new(StatusCode::NOT_FOUND, "")),
+        );
+        let cache = Arc::new(MemoryCache::new(10));
+        let client = cached_client(
+            transport.clone(),
+            CacheConfig::new(cache.clone()).ttl("/shows/:id/akas", Duration::ZERO),
+        );
+
+        client.show_akas(1).await.unwrap();
+        assert!(client.show_akas(1).await.unwrap().is_none());
+        assert!(cache.get(&client.url("/shows/1/akas")).is_none());
+    }
+}
@@ -0,0 +1,534 @@
+use std::{sync::Arc, time::Duration};
+
+use reqwest::{
+    header::{HeaderMap, HeaderValue, IF_MODIFIED_SINCE, IF_NONE_MATCH, USER_AGENT},
+    StatusCode,
+};
+#[cfg(feature = "user-api")]
+use reqwest::{
+    header::{AUTHORIZATION, CONTENT_TYPE},
+    Method,
+};
+use serde::de::DeserializeOwned;
+
+use crate::{
+    cache::{CacheConfig, CacheEntry},
+    rate_limit::{RateLimit, RateLimiter, RetryPolicy},
+    response::ResponseKind,
+    transport::{Request, ReqwestTransport, Response, Transport},
+    Error, Result,
+};
+
+/// Base URL of the public TVmaze API.
+pub const DEFAULT_BASE_URL: &str = "https://api.tvmaze.com";
+
+/// User agent sent with every request unless overridden.
+pub const DEFAULT_USER_AGENT: &str =
+    concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION"));
+
+/// Client for the TVmaze API. Cloning is cheap, clones share the same transport and thus connection pool.
+#[derive(Clone, Debug)]
+pub struct TvMazeClient {
+    transport: Arc<dyn Transport>,
+    base_url: String,
+    user_agent: HeaderValue,
+    rate_limiter: Option<Arc<RateLimiter>>,
+    retry_policy: RetryPolicy,
+    cache: Option<CacheConfig>,
+    #[cfg(feature = "user-api")]
+    credentials: Option<HeaderValue>,
+}
+
+impl TvMazeClient {
+    /// Create a client with the default configuration, pointed at the public TVmaze API.
+    pub fn new() -> Self {
+        TvMazeClientBuilder::new()
+            .build()
+            .expect("default client configuration is valid")
+    }
+
+    pub fn builder() -> TvMazeClientBuilder {
+        TvMazeClientBuilder::new()
+    }
+
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    pub(crate) fn url(&self, path: &str) -> String {
+        format!("{}{}", self.base_url, path)
+    }
+
+    pub(crate) async fn get<T: DeserializeOwned>(&self, path: &str) -> Result<T> {
+        match self.get_notfoundable(path).await? {
+            Some(res) => Ok(res),
+            None => Err(Error::Status {
+                status: StatusCode::NOT_FOUND,
+                body: String::new(),
+            }),
+        }
+    }
+
+    pub(crate) async fn get_notfoundable<T: DeserializeOwned>(
+        &self,
+        path: &str,
+    ) -> Result<Option<T>> {
+        match self.fetch(path).await? {
+            Some(body) => parse(path, &body).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Body of a successful response to `path`, or `None` on a 404. Goes through the cache if one is configured.
+    pub(crate) async fn fetch(&self, path: &str) -> Result<Option<String>> {
+        let url = self.url(path);
+        let cached = self.cache.as_ref().and_then(|c| c.backend().get(&url));
+        if let Some(entry) = cached.as_ref().filter(|entry| entry.is_fresh()) {
+            return Ok(Some(entry.body.clone()));
+        }
+
+        let (res, kind) = self
+            .send(|| {
+                let mut req = Request::get(&url);
+                if let Some(entry) = &cached {
+                    conditional_headers(&mut req.headers, entry);
+                }
+                req
+            })
+            .await?;
+
+        if let (Some(cache), Some(mut entry)) = (&self.cache, cached) {
+            match res.status {
+                StatusCode::NOT_MODIFIED => {
+                    let body = entry.body.clone();
+                    if cache.refresh(path, &res.headers, &mut entry) {
+                        cache.backend().put(&url, entry);
+                    } else {
+                        cache.backend().remove(&url);
+                    }
+                    return Ok(Some(body));
+                }
+                StatusCode::NOT_FOUND => cache.backend().remove(&url),
+                _ => {}
+            }
+        }
+
+        if kind == ResponseKind::Success {
+            if let Some(cache) = &self.cache {
+                if let Some(entry) = cache.entry(path, &res.headers, res.body.clone()) {
+                    cache.backend().put(&url, entry);
+                }
+            }
+        }
+        outcome(res, kind)
+    }
+
+    /// Send the request built by `request`, waiting for the rate limiter and retrying as configured. Server errors are only retried for idempotent methods. A rate limited response asking to wait longer than the retry policy's `max_delay` is returned right away instead.
+    async fn send(&self, request: impl Fn() -> Request) -> Result<(Response, ResponseKind)> {
+        let mut attempt = 0;
+        loop {
+            if let Some(rate_limiter) = &self.rate_limiter {
+                rate_limiter.acquire().await;
+            }
+
+            let mut req = request();
+            req.headers.insert(USER_AGENT, self.user_agent.clone());
+            let idempotent = req.method.is_idempotent();
+
+            let res = self.transport.send(req).await?;
+            let kind = ResponseKind::classify(res.status, &res.headers);
+
+            let retryable = match kind {
+                ResponseKind::ServerError => idempotent,
+                ref kind => kind.is_retryable(),
+            };
+            if retryable && attempt < self.retry_policy.max_retries {
+                let delay = match kind {
+                    ResponseKind::RateLimited {
+                        retry_after: Some(retry_after),
+                    } => retry_after,
+                    _ => self.retry_policy.delay(attempt),
+                };
+                if delay > self.retry_policy.max_delay {
+                    return Ok((res, kind));
+                }
+                tokio::time::sleep(delay).await;
+                attempt += 1;
+                continue;
+            }
+
+            return Ok((res, kind));
+        }
+    }
+
+    /// Send an authenticated request to the user API. Responses are never cached.
+    #[cfg(feature = "user-api")]
+    pub(crate) async fn send_user(
+        &self,
+        method: Method,
+        path: &str,
+        body: Option<String>,
+    ) -> Result<Option<String>> {
+        let credentials = self
+            .credentials
+            .as_ref()
+            .ok_or(Error::InvalidConfig("the user API requires credentials"))?;
+        let url = self.url(path);
+
+        let (res, kind) = self
+            .send(|| {
+                let mut req = Request::get(&url);
+                req.method = method.clone();
+                req.headers.insert(AUTHORIZATION, credentials.clone());
+                if let Some(body) = &body {
+                    req.headers
+                        .insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+                    req.body = Some(body.clone());
+                }
+                req
+            })
+            .await?;
+        outcome(res, kind)
+    }
+
+    /// The cache configuration, if caching is enabled.
+    pub fn cache(&self) -> Option<&CacheConfig> {
+        self.cache.as_ref()
+    }
+}
+
+/// Body of a successful response, `None` on a 404, or the error the response stands for.
+fn outcome(res: Response, kind: ResponseKind) -> Result<Option<String>> {
+    let status = res.status;
+    match kind {
+        ResponseKind::Success => Ok(Some(res.body)),
+        ResponseKind::NotFound => Ok(None),
+        ResponseKind::RateLimited { retry_after } => Err(Error::RateLimited { retry_after }),
+        ResponseKind::ServerError => Err(Error::ServerError {
+            status,
+            body: res.body,
+        }),
+        ResponseKind::Redirect { location } => Err(Error::Redirect { status, location }),
+        ResponseKind::ClientError => Err(Error::Status {
+            status,
+            body: res.body,
+        }),
+    }
+}
+
+/// Append `query` to `path`, leaving out the `?` if there is nothing to append.
+pub(crate) fn with_query(path: &str, query: impl std::fmt::Display) -> String {
+    let query = query.to_string();
+    if query.is_empty() {
+        path.to_string()
+    } else {
+        format!("{}?{}", path, query)
+    }
+}
+
+fn conditional_headers(headers: &mut HeaderMap, entry: &CacheEntry) {
+    if let Some(etag) = entry
+        .etag
+        .as_deref()
+        .and_then(|v| HeaderValue::from_str(v).ok())
+    {
+        headers.insert(IF_NONE_MATCH, etag);
+    }
+    if let Some(last_modified) = entry
+        .last_modified
+        .as_deref()
+        .and_then(|v| HeaderValue::from_str(v).ok())
+    {
+        headers.insert(IF_MODIFIED_SINCE, last_modified);
+    }
+}
+
+/// Deserialize `body`, keeping track of where in the document a failure happened.
+pub(crate) fn parse<T: DeserializeOwned>(endpoint: &str, body: &str) -> Result<T> {
+    let mut de = serde_json::Deserializer::from_str(body);
+    let res = serde_path_to_error::deserialize(&mut de).map_err(|e| Error::Deserialize {
+        endpoint: endpoint.to_string(),
+        path: e.path().to_string(),
+        source: e.into_inner(),
+    })?;
+
+    de.end().map_err(|e| Error::Deserialize {
+        endpoint: endpoint.to_string(),
+        path: ".".to_string(),
+        source: e,
+    })?;
+
+    Ok(res)
+}
+
+impl Default for TvMazeClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Builder for [`TvMazeClient`].
+#[derive(Debug)]
+pub struct TvMazeClientBuilder {
+    base_url: String,
+    user_agent: String,
+    timeout: Option<Duration>,
+    connect_timeout: Option<Duration>,
+    rate_limit: Option<RateLimit>,
+    retry_policy: RetryPolicy,
+    transport: Option<Arc<dyn Transport>>,
+    cache: Option<CacheConfig>,
+    #[cfg(feature = "user-api")]
+    credentials: Option<(String, String)>,
+}
+
+impl TvMazeClientBuilder {
+    pub fn new() -> Self {
+        Self {
+            base_url: DEFAULT_BASE_URL.to_string(),
+            user_agent: DEFAULT_USER_AGENT.to_string(),
+            timeout: Some(Duration::from_secs(30)),
+            connect_timeout: Some(Duration::from_secs(10)),
+            rate_limit: Some(RateLimit::default()),
+            retry_policy: RetryPolicy::default(),
+            transport: None,
+            cache: None,
+            #[cfg(feature = "user-api")]
+            credentials: None,
+        }
+    }
+
+    /// Base URL requests are made against, e.g. a local stand-in server. A trailing slash is ignored.
+    pub fn base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    pub fn user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.user_agent = user_agent.into();
+        self
+    }
+
+    /// Total timeout for a single request, `None` disables it. Only applies to the default transport.
+    pub fn timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Timeout for establishing a connection, `None` disables it. Only applies to the default transport.
+    pub fn connect_timeout(mut self, connect_timeout: Option<Duration>) -> Self {
+        self.connect_timeout = connect_timeout;
+        self
+    }
+
+    /// Client-side rate limit shared by all clones of the built client, `None` disables it.
+    pub fn rate_limit(mut self, rate_limit: Option<RateLimit>) -> Self {
+        self.rate_limit = rate_limit;
+        self
+    }
+
+    /// Retry behaviour for rate limited requests and server errors, see [`RetryPolicy::NONE`] to disable retries.
+    pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Perform requests through `transport` instead of the default [`ReqwestTransport`].
+    pub fn transport(mut self, transport: Arc<dyn Transport>) -> Self {
+        self.transport = Some(transport);
+        self
+    }
+
+    /// Cache successful responses as described by `cache`. Disabled by default.
+    pub fn cache(mut self, cache: CacheConfig) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// Username and API key of a TVmaze premium account, sent as basic auth to the user API only.
+    #[cfg(feature = "user-api")]
+    pub fn credentials(mut self, username: impl Into<String>, api_key: impl Into<String>) -> Self {
+        self.credentials = Some((username.into(), api_key.into()));
+        self
+    }
+
+    pub fn build(self) -> Result<TvMazeClient> {
+        if self.rate_limit.is_some_and(|limit| limit.calls == 0) {
+            return Err(Error::InvalidConfig(
+                "the rate limit must allow at least one call",
+            ));
+        }
+
+        let transport = match self.transport {
+            Some(transport) => transport,
+            None => {
+                let mut http = reqwest::Client::builder();
+                if let Some(timeout) = self.timeout {
+                    http = http.timeout(timeout);
+                }
+                if let Some(connect_timeout) = self.connect_timeout {
+                    http = http.connect_timeout(connect_timeout);
+                }
+                Arc::new(ReqwestTransport::new(http.build()?))
+            }
+        };
+
+        Ok(TvMazeClient {
+            transport,
+            base_url: self.base_url,
+            user_agent: HeaderValue::from_str(&self.user_agent)
+                .map_err(|_| Error::InvalidConfig("user agent is not a valid header value"))?,
+            rate_limiter: self
+                .rate_limit
+                .map(|limit| Arc::new(RateLimiter::new(limit))),
+            retry_policy: self.retry_policy,
+            cache: self.cache,
+            #[cfg(feature = "user-api")]
+            credentials: self
+                .credentials
+                .map(|(username, api_key)| basic_auth(&username, &api_key))
+                .transpose()?,
+        })
+    }
+}
+
+#[cfg(feature = "user-api")]
+fn basic_auth(username: &str, api_key: &str) -> Result<HeaderValue> {
+    use base64::Engine;
+
+    let encoded =
+        base64::engine::general_purpose::STANDARD.encode(format!("{}:{}", username, api_key));
+    let mut value = HeaderValue::from_str(&format!("Basic {}", encoded))
+        .map_err(|_| Error::InvalidConfig("credentials are not a valid header value"))?;
+    value.set_sensitive(true);
+    Ok(value)
+}
+
+impl Default for TvMazeClientBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::Arc, time::Duration};
+
+    use reqwest::{
+        header::{HeaderValue, RETRY_AFTER, USER_AGENT},
+        StatusCode,
+    };
+
+    use super::{parse, TvMazeClient, DEFAULT_USER_AGENT};
+    use crate::{
+        models::Rating,
+        tests::memory_client,
+        transport::{MemoryTransport, Response},
+        Error, RateLimit, UpdateWindow,
+    };
+
+    #[test]
+    fn test_base_url() {
+        let client = TvMazeClient::builder()
+            .base_url("http://localhost:8080/")
+            .build()
+            .unwrap();
+
+        assert_eq!(client.url("/shows/1"), "http://localhost:8080/shows/1");
+    }
+
+    #[test]
+    fn test_parse_error_path() {
+        match parse::<Vec<Rating>>("/test", r#"[{"average": 1.5}, {"average": "high"}]"#) {
+            Err(Error::Deserialize { endpoint, path, .. }) => {
+                assert_eq!(endpoint, "/test");
+                assert_eq!(path, "[1].average");
+            }
+            res => panic!("unexpected result {:?}", res),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_not_found() {
+        let client = memory_client(Arc::new(MemoryTransport::new()));
+
+        assert!(client.show_akas(1).await.unwrap().is_none());
+        assert!(matches!(
+            client.show_updates(UpdateWindow::All).await,
+            Err(Error::Status {
+                status: StatusCode::NOT_FOUND,
+                ..
+            })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_retry() {
+        let transport = Arc::new(
+            MemoryTransport::new()
+                .with_response(
+                    "/shows/1/akas",
+                    Response::new(StatusCode::SERVICE_UNAVAILABLE, "maintenance"),
+                )
+                .with_response(
+                    "/shows/1/akas",
+                    Response::new(StatusCode::TOO_MANY_REQUESTS, ""),
+                )
+                .with_json("/shows/1/akas", r#"[{"name": "Aka", "country": null}]"#),
+        );
+        let client = memory_client(transport.clone());
+
+        let akas = client.show_akas(1).await.unwrap().unwrap();
+        assert_eq!(akas[0].name, "Aka");
+
+        let requests = transport.requests();
+        assert_eq!(requests.len(), 3);
+        assert_eq!(requests[0].headers[USER_AGENT], DEFAULT_USER_AGENT);
+    }
+
+    #[tokio::test]
+    async fn test_retries_exhausted() {
+        let transport = Arc::new(MemoryTransport::new().with_response(
+            "/shows/1/akas",
+            Response::new(StatusCode::BAD_GATEWAY, "bad gateway"),
+        ));
+        let client = memory_client(transport.clone());
+
+        match client.show_akas(1).await {
+            Err(Error::ServerError { status, body }) => {
+                assert_eq!(status, StatusCode::BAD_GATEWAY);
+                assert_eq!(body, "bad gateway");
+            }
+            res => panic!("unexpected result {:?}", res),
+        }
+        assert_eq!(transport.requests().len(), 3);
+    }
+
+    #[test]
+    fn test_zero_rate_limit() {
+        let res = TvMazeClient::builder()
+            .rate_limit(Some(RateLimit {
+                calls: 0,
+                per: Duration::from_secs(10),
+            }))
+            .build();
+        assert!(matches!(res, Err(Error::InvalidConfig(_))));
+    }
+
+    #[tokio::test]
+    async fn test_retry_after_beyond_max_delay() {
+        let mut res = Response::new(StatusCode::TOO_MANY_REQUESTS, "");
+        res.headers
+            .insert(RETRY_AFTER, HeaderValue::from_static("3600"));
+        let transport = Arc::new(MemoryTransport::new().with_response("/shows/1/akas", res));
+        let client = memory_client(transport.clone());
+
+        match client.show_akas(1).await {
+            Err(Error::RateLimited { retry_after }) => {
+                assert_eq!(retry_after, Some(Duration::from_secs(3600)));
+            }
+            res => panic!("unexpected result {:?}", res),
+        }
+        assert_eq!(transport.requests().len(), 1);
+    }
+}
@@ -0,0 +1,317 @@
+//! Typed embedding. Each endpoint that supports embedding takes a set of embeds valid for its resource: a single marker like [`Cast`], a tuple of markers like `(Cast, Episodes)`, or `()` for none. The `_embedded` field of the returned resource then holds exactly what was requested, e.g. a `Vec<CastMember>` for [`Cast`] or a `(Vec<CastMember>, Vec<Episode>)` for `(Cast, Episodes)`.
+
+use std::{fmt::Debug, ops::Deref};
+
+use serde::{
+    de::{DeserializeOwned, Error as _},
+    Deserialize, Deserializer,
+};
+use serde_json::{Map, Value};
+
+use crate::models;
+
+/// A single resource that can be embedded.
+pub trait Embeddable {
+    /// Name of the embed, both in the request and in `_embedded`.
+    const NAME: &'static str;
+    type Embedded: DeserializeOwned + Debug;
+}
+
+/// A set of embeds: a single [`Embeddable`], a tuple of them, or `()` for none.
+pub trait EmbedSet {
+    type Embedded: Debug;
+
+    fn names(&self) -> Vec<&'static str>;
+
+    #[doc(hidden)]
+    fn extract(embedded: &mut Map<String, Value>) -> Result<Self::Embedded, serde_json::Error>;
+}
+
+/// Embeds supported by shows.
+pub trait ShowEmbed: EmbedSet {}
+/// Embeds supported by episodes.
+pub trait EpisodeEmbed: EmbedSet {}
+/// Embeds supported by people.
+pub trait PersonEmbed: EmbedSet {}
+/// Embeds supported by cast credits.
+pub trait CreditEmbed: EmbedSet {}
+/// Embeds supported by crew credits, which unlike cast credits have no character.
+pub trait CrewCreditEmbed: EmbedSet {}
+/// Embeds supported by seasons.
+pub trait SeasonEmbed: EmbedSet {}
+/// Embeds supported by alternate lists.
+pub trait AlternateListEmbed: EmbedSet {}
+/// Embeds supported by alternate episodes.
+pub trait AlternateEpisodeEmbed: EmbedSet {}
+
+/// Query string requesting `embed`, empty if nothing is embedded.
+pub(crate) fn query<E: EmbedSet>(embed: &E) -> String {
+    embed
+        .names()
+        .iter()
+        .map(|name| format!("embed[]={}", name))
+        .collect::<Vec<_>>()
+        .join("&")
+}
+
+fn extract_one<T: Embeddable>(
+    embedded: &mut Map<String, Value>,
+) -> Result<T::Embedded, serde_json::Error> {
+    // Embeds with nothing to show, like the next episode of an ended show, are left out entirely.
+    let value = embedded.remove(T::NAME).unwrap_or(Value::Null);
+    serde_json::from_value(value)
+        .map_err(|e| serde_json::Error::custom(format!("{}: {}", T::NAME, e)))
+}
+
+impl EmbedSet for () {
+    type Embedded = ();
+
+    fn names(&self) -> Vec<&'static str> {
+        Vec::new()
+    }
+
+    fn extract(_: &mut Map<String, Value>) -> Result<Self::Embedded, serde_json::Error> {
+        Ok(())
+    }
+}
+
+impl<T: Embeddable> EmbedSet for T {
+    type Embedded = T::Embedded;
+
+    fn names(&self) -> Vec<&'static str> {
+        vec![T::NAME]
+    }
+
+    fn extract(embedded: &mut Map<String, Value>) -> Result<Self::Embedded, serde_json::Error> {
+        extract_one::<T>(embedded)
+    }
+}
+
+macro_rules! tuple_embeds {
+    ($($t:ident),+) => {
+        impl<$($t: Embeddable),+> EmbedSet for ($($t,)+) {
+            type Embedded = ($($t::Embedded,)+);
+
+            fn names(&self) -> Vec<&'static str> {
+                vec![$($t::NAME),+]
+            }
+
+            fn extract(embedded: &mut Map<String, Value>) -> Result<Self::Embedded, serde_json::Error> {
+                Ok(($(extract_one::<$t>(embedded)?,)+))
+            }
+        }
+
+        impl<$($t: Embeddable + ShowEmbed),+> ShowEmbed for ($($t,)+) {}
+        impl<$($t: Embeddable + EpisodeEmbed),+> EpisodeEmbed for ($($t,)+) {}
+        impl<$($t: Embeddable + PersonEmbed),+> PersonEmbed for ($($t,)+) {}
+        impl<$($t: Embeddable + CreditEmbed),+> CreditEmbed for ($($t,)+) {}
+        impl<$($t: Embeddable + CrewCreditEmbed),+> CrewCreditEmbed for ($($t,)+) {}
+        impl<$($t: Embeddable + SeasonEmbed),+> SeasonEmbed for ($($t,)+) {}
+        impl<$($t: Embeddable + AlternateListEmbed),+> AlternateListEmbed for ($($t,)+) {}
+        impl<$($t: Embeddable + AlternateEpisodeEmbed),+> AlternateEpisodeEmbed for ($($t,)+) {}
+    };
+}
+
+tuple_embeds!(A, B);
+tuple_embeds!(A, B, C);
+tuple_embeds!(A, B, C, D);
+tuple_embeds!(A, B, C, D, E);
+tuple_embeds!(A, B, C, D, E, F);
+tuple_embeds!(A, B, C, D, E, F, G);
+tuple_embeds!(A, B, C, D, E, F, G, H);
+
+impl ShowEmbed for () {}
+impl EpisodeEmbed for () {}
+impl PersonEmbed for () {}
+impl CreditEmbed for () {}
+impl CrewCreditEmbed for () {}
+impl SeasonEmbed for () {}
+impl AlternateListEmbed for () {}
+impl AlternateEpisodeEmbed for () {}
+
+macro_rules! embeddable {
+    ($(#[$attr:meta])* $marker:ident = $name:literal => $embedded:ty: $($resource:ident),+) => {
+        $(#[$attr])*
+        #[derive(Clone, Copy, Debug, Default)]
+        pub struct $marker;
+
+        impl Embeddable for $marker {
+            const NAME: &'static str = $name;
+            type Embedded = $embedded;
+        }
+
+        $(impl $resource for $marker {})+
+    };
+}
+
+embeddable!(
+    /// All episodes of a show or season, or the episodes an alternate episode stands for.
+    Episodes = "episodes" => Vec<models::Episode>: ShowEmbed, SeasonEmbed, AlternateEpisodeEmbed
+);
+embeddable!(
+    /// Main cast of a show.
+    Cast = "cast" => Vec<models::CastMember>: ShowEmbed
+);
+embeddable!(
+    /// The next episode of a show, if one is announced.
+    NextEpisode = "nextepisode" => Option<models::Episode>: ShowEmbed
+);
+embeddable!(
+    /// The latest aired episode of a show, if any.
+    PreviousEpisode = "previousepisode" => Option<models::Episode>: ShowEmbed
+);
+embeddable!(
+    /// Main crew of a show.
+    Crew = "crew" => Vec<models::CrewMember>: ShowEmbed
+);
+embeddable!(
+    /// Seasons of a show.
+    Seasons = "seasons" => Vec<models::Season>: ShowEmbed
+);
+embeddable!(
+    /// All images of a show.
+    Images = "images" => Vec<models::ShowImage>: ShowEmbed
+);
+embeddable!(
+    /// Aliases of a show.
+    Akas = "akas" => Vec<models::Aka>: ShowEmbed
+);
+embeddable!(
+    /// The show an episode or credit belongs to.
+    Show = "show" => models::Show: EpisodeEmbed, CreditEmbed, CrewCreditEmbed
+);
+embeddable!(
+    /// Guest cast of an episode.
+    GuestCast = "guestcast" => Vec<models::CastMember>: EpisodeEmbed
+);
+embeddable!(
+    /// Guest crew of an episode.
+    GuestCrew = "guestcrew" => Vec<models::CrewMember>: EpisodeEmbed
+);
+embeddable!(
+    /// Cast credits of a person.
+    CastCredits = "castcredits" => Vec<models::CastCredit>: PersonEmbed
+);
+embeddable!(
+    /// Crew credits of a person.
+    CrewCredits = "crewcredits" => Vec<models::CrewCredit>: PersonEmbed
+);
+embeddable!(
+    /// Episodes of an alternate list, in the alternate order.
+    AlternateEpisodes = "alternateepisodes" => Vec<models::AlternateEpisode>: AlternateListEmbed
+);
+embeddable!(
+    /// The character of a cast credit.
+    Character = "character" => models::Character: CreditEmbed
+);
+
+#[cfg(feature = "user-api")]
+mod user {
+    use super::{EmbedSet, Embeddable, Show};
+    use crate::models;
+
+    /// Embeds supported by followed shows.
+    pub trait FollowedShowEmbed: EmbedSet {}
+    /// Embeds supported by followed people.
+    pub trait FollowedPersonEmbed: EmbedSet {}
+    /// Embeds supported by followed networks.
+    pub trait FollowedNetworkEmbed: EmbedSet {}
+    /// Embeds supported by followed web channels.
+    pub trait FollowedWebChannelEmbed: EmbedSet {}
+    /// Embeds supported by episode markings.
+    pub trait MarkingEmbed: EmbedSet {}
+    /// Embeds supported by show votes.
+    pub trait ShowVoteEmbed: EmbedSet {}
+    /// Embeds supported by episode votes.
+    pub trait EpisodeVoteEmbed: EmbedSet {}
+
+    impl FollowedShowEmbed for () {}
+    impl FollowedPersonEmbed for () {}
+    impl FollowedNetworkEmbed for () {}
+    impl FollowedWebChannelEmbed for () {}
+    impl MarkingEmbed for () {}
+    impl ShowVoteEmbed for () {}
+    impl EpisodeVoteEmbed for () {}
+
+    impl FollowedShowEmbed for Show {}
+    impl ShowVoteEmbed for Show {}
+
+    embeddable!(
+        /// A marked or voted episode.
+        Episode = "episode" => models::Episode: MarkingEmbed, EpisodeVoteEmbed
+    );
+
+    embeddable!(
+        /// A followed person.
+        Person = "person" => models::Person: FollowedPersonEmbed
+    );
+    embeddable!(
+        /// A followed network.
+        Network = "network" => models::Network: FollowedNetworkEmbed
+    );
+    embeddable!(
+        /// A followed web channel.
+        WebChannel = "webchannel" => models::WebChannel: FollowedWebChannelEmbed
+    );
+}
+#[cfg(feature = "user-api")]
+pub use user::*;
+
+/// The `_embedded` field of a resource, dereferencing to what `E` requested.
+pub struct Embedded<E: EmbedSet>(E::Embedded);
+
+impl<E: EmbedSet> Embedded<E> {
+    pub fn into_inner(self) -> E::Embedded {
+        self.0
+    }
+}
+
+impl<E: EmbedSet> Deref for Embedded<E> {
+    type Target = E::Embedded;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<E: EmbedSet> Debug for Embedded<E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<'de, E: EmbedSet> Deserialize<'de> for Embedded<E> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        // A missing `_embedded` is read as `None`, which is fine as long as nothing was requested.
+        let mut embedded =
+            Option::<Map<String, Value>>::deserialize(deserializer)?.unwrap_or_default();
+        E::extract(&mut embedded)
+            .map(Embedded)
+            .map_err(D::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{query, Cast, Embedded, Episodes, NextEpisode};
+
+    #[test]
+    fn test_query() {
+        assert_eq!(query(&()), "");
+        assert_eq!(query(&Cast), "embed[]=cast");
+        assert_eq!(
+            query(&(Episodes, Cast, NextEpisode)),
+            "embed[]=episodes&embed[]=cast&embed[]=nextepisode"
+        );
+    }
+
+    #[test]
+    fn test_missing_embed() {
+        let res: Embedded<(Cast, NextEpisode)> = serde_json::from_str(r#"{"cast": []}"#).unwrap();
+        let (cast, next) = &*res;
+        assert!(cast.is_empty());
+        assert!(next.is_none());
+
+        assert!(serde_json::from_str::<Embedded<Cast>>("{}").is_err());
+    }
+}
@@ -1,18 +1,128 @@
-use reqwest::StatusCode;
+use crate::{
+    client::with_query,
+    embed::{self, EpisodeEmbed, GuestCast, GuestCrew},
+    models::{CastMember, CrewMember, Episode, EpisodeGuests},
+    Result, TvMazeClient,
+};
 
-use crate::models::Episode;
+impl TvMazeClient {
+    /// Retrieve all primary information for a given episode. This endpoint allows embedding of additional information. See the section embedding for more information.
+    pub async fn episode<E: EpisodeEmbed>(
+        &self,
+        id: usize,
+        embed: E,
+    ) -> Result<Option<Episode<E>>> {
+        self.get_notfoundable(&with_query(
+            &format!("/episodes/{}", id),
+            embed::query(&embed),
+        ))
+        .await
+    }
+
+    /// Retrieve an episode's guest cast, the people playing a character in this episode only.
+    pub async fn episode_guest_cast(&self, id: usize) -> Result<Option<Vec<CastMember>>> {
+        self.get_notfoundable(&format!("/episodes/{}/guestcast", id))
+            .await
+    }
+
+    /// Retrieve an episode's guest crew, the people credited for working on this episode only.
+    pub async fn episode_guest_crew(&self, id: usize) -> Result<Option<Vec<CrewMember>>> {
+        self.get_notfoundable(&format!("/episodes/{}/guestcrew", id))
+            .await
+    }
+
+    /// Guest cast and crew of every episode of a show, in airing order. Takes one request per episode on top of the episode list; filter on `episode.season` to get the guests of a single season.
+    pub async fn show_guests(
+        &self,
+        show: usize,
+        specials: bool,
+    ) -> Result<Option<Vec<EpisodeGuests>>> {
+        let Some(episodes) = self.show_episode_list(show, specials).await? else {
+            return Ok(None);
+        };
 
-/// Retrieve all primary information for a given episode. This endpoint allows embedding of additional information. See the section embedding for more information.
-pub async fn episode(id: usize) -> Result<Option<Episode>, reqwest::Error> {
-    crate::notfoundable_endpoint!(format!("https://api.tvmaze.com/episodes/{}", id,))
+        let mut guests = Vec::with_capacity(episodes.len());
+        for episode in episodes {
+            // Episodes deleted since the list was fetched are skipped.
+            if let Some(embedded) = self.episode(episode.id, (GuestCast, GuestCrew)).await? {
+                let (cast, crew) = embedded._embedded.into_inner();
+                guests.push(EpisodeGuests {
+                    episode,
+                    cast,
+                    crew,
+                });
+            }
+        }
+
+        Ok(Some(guests))
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::episode;
+    use crate::{
+        embed::{GuestCast, GuestCrew, Show},
+        tests::fixture_client,
+    };
 
     #[tokio::test]
     async fn test_episode() {
-        episode(1).await.unwrap().unwrap();
+        let client = fixture_client();
+        client.episode(1, ()).await.unwrap().unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_episode_embed() {
+        let client = fixture_client();
+        let res = client
+            .episode(1, (Show, GuestCast, GuestCrew))
+            .await
+            .unwrap()
+            .unwrap();
+        let (show, guestcast, guestcrew) = res._embedded.into_inner();
+
+        assert_eq!(show.id, 1);
+        assert_eq!(guestcast.len(), 1);
+        assert_eq!(guestcrew[0].r#type, "Director");
+    }
+
+    #[tokio::test]
+    async fn test_episode_guest_cast() {
+        let client = fixture_client();
+        let cast = client.episode_guest_cast(1).await.unwrap().unwrap();
+        assert_eq!(cast[0].character.name, "Norrie Calvert-Hill");
+    }
+
+    #[tokio::test]
+    async fn test_episode_guest_crew() {
+        let client = fixture_client();
+        let crew = client.episode_guest_crew(1).await.unwrap().unwrap();
+        assert_eq!(crew[0].r#type, "Director");
+    }
+
+    #[tokio::test]
+    async fn test_show_guests() {
+        let client = fixture_client();
+        let guests = client.show_guests(52430, false).await.unwrap().unwrap();
+
+        assert_eq!(guests.len(), 9);
+        assert_eq!(
+            guests
+                .iter()
+                .filter(|guests| guests
+                    .cast
+                    .iter()
+                    .any(|cast| cast.person.name == "Jing Lusi"))
+                .map(|guests| guests.episode.number.unwrap())
+                .collect::<Vec<_>>(),
+            [1, 2, 9]
+        );
+        assert_eq!(
+            guests
+                .iter()
+                .filter(|guests| !guests.crew.is_empty())
+                .count(),
+            5
+        );
     }
 }
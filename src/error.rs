@@ -0,0 +1,103 @@
+use std::{fmt::Display, time::Duration};
+
+use reqwest::StatusCode;
+
+/// Errors returned by every endpoint of this crate.
+#[derive(Debug)]
+pub enum Error {
+    /// The request could not be sent or its response could not be read.
+    Transport(reqwest::Error),
+    /// The server rejected the request with a client error status the endpoint does not accept.
+    Status { status: StatusCode, body: String },
+    /// The server failed to handle the request, e.g. during maintenance.
+    ServerError { status: StatusCode, body: String },
+    /// The server answered with a redirect that was not followed.
+    Redirect {
+        status: StatusCode,
+        location: Option<String>,
+    },
+    /// The response body did not match the expected model.
+    Deserialize {
+        endpoint: String,
+        path: String,
+        source: serde_json::Error,
+    },
+    /// The server rejected the request because too many calls were made.
+    RateLimited { retry_after: Option<Duration> },
+    /// The client was configured with invalid settings.
+    InvalidConfig(&'static str),
+    /// An argument was outside the values the endpoint accepts, so no request was sent.
+    InvalidArgument(&'static str),
+    /// Reading or writing local files failed.
+    Io(std::io::Error),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Transport(e) => write!(f, "transport error: {}", e),
+            Error::Status { status, .. } => write!(f, "unexpected HTTP status {}", status),
+            Error::ServerError { status, .. } => write!(f, "server error {}", status),
+            Error::Redirect {
+                status,
+                location: Some(location),
+            } => write!(f, "unfollowed redirect {} to {}", status, location),
+            Error::Redirect {
+                status,
+                location: None,
+            } => write!(f, "unfollowed redirect {}", status),
+            Error::Deserialize {
+                endpoint,
+                path,
+                source,
+            } => write!(
+                f,
+                "failed to deserialize response of {} at `{}`: {}",
+                endpoint, path, source
+            ),
+            Error::RateLimited {
+                retry_after: Some(retry_after),
+            } => write!(f, "rate limited, retry after {:?}", retry_after),
+            Error::RateLimited { retry_after: None } => f.write_str("rate limited"),
+            Error::InvalidConfig(reason) => write!(f, "invalid configuration: {}", reason),
+            Error::InvalidArgument(reason) => write!(f, "invalid argument: {}", reason),
+            Error::Io(e) => write!(f, "io error: {}", e),
+        }
+    }
+}
+
+impl Error {
+    /// Whether the same request may succeed when sent again later.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Error::Transport(e) => e.is_timeout() || e.is_connect(),
+            Error::RateLimited { .. } | Error::ServerError { .. } => true,
+            _ => false,
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Transport(e) => Some(e),
+            Error::Deserialize { source, .. } => Some(source),
+            Error::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<reqwest::Error> for Error {
+    fn from(e: reqwest::Error) -> Self {
+        Error::Transport(e)
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::Io(e)
+    }
+}
+
+pub type Result<T, E = Error> = std::result::Result<T, E>;
@@ -0,0 +1,45 @@
+use std::future::{ready, Future};
+
+use futures_util::{stream, Stream, StreamExt};
+
+use crate::Result;
+
+/// Number of index pages requested concurrently: the page being consumed and the ones after it.
+const PREFETCH: usize = 2;
+
+/// Walk the pages of an index from `first` until the first 404, yielding the items `keep` accepts. The stream ends after the first error.
+pub(crate) fn index_stream<T, F, Fut>(
+    first: usize,
+    fetch: F,
+    keep: impl Fn(&T) -> bool,
+) -> impl Stream<Item = Result<T>>
+where
+    F: FnMut(usize) -> Fut,
+    Fut: Future<Output = Result<Option<Vec<T>>>>,
+{
+    stream::iter(first..)
+        .map(fetch)
+        .buffered(PREFETCH)
+        .scan(false, |failed, page| {
+            let page = match page {
+                _ if *failed => None,
+                Ok(page) => page.map(Ok),
+                Err(e) => {
+                    *failed = true;
+                    Some(Err(e))
+                }
+            };
+            ready(page)
+        })
+        .flat_map(move |page| {
+            let items: Vec<_> = match page {
+                Ok(items) => items
+                    .into_iter()
+                    .filter(|item| keep(item))
+                    .map(Ok)
+                    .collect(),
+                Err(e) => vec![Err(e)],
+            };
+            stream::iter(items)
+        })
+}
@@ -0,0 +1,539 @@
+//! A local copy of the whole TVmaze database, kept current with the update endpoints.
+
+use std::{
+    collections::BTreeSet,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use chrono::{DateTime, Utc};
+use futures_util::StreamExt;
+use serde::{de::IgnoredAny, Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{
+    cache::covering_window,
+    client::{parse, with_query},
+    embed::{self, Cast, Episodes, Seasons},
+    index::index_stream,
+    models::{Person, PersonId, Show, ShowId},
+    people::PERSON_INDEX_PAGE_SIZE,
+    shows::SHOW_INDEX_PAGE_SIZE,
+    Result, TvMazeClient,
+};
+
+/// A show as stored after a sync refetched it, with its episodes, seasons and cast.
+pub type MirroredShow = Show<(Episodes, Seasons, Cast)>;
+
+/// Number of records stored between saves of the crawl progress.
+const CHECKPOINT: usize = 250;
+
+/// Mirrors every show and person of TVmaze into a directory, one JSON file per record.
+///
+/// [`Mirror::crawl`] walks the show and person indexes, storing every record as listed there. [`Mirror::sync`] then refetches the shows and people that changed since, storing shows together with their episodes, seasons and cast. Progress is saved to the directory as it is made, so both resume where they stopped after a crash. Only one mirror may use a directory at a time.
+#[derive(Debug)]
+pub struct Mirror {
+    client: TvMazeClient,
+    dir: PathBuf,
+    state: State,
+}
+
+/// Records refetched by [`Mirror::sync`].
+#[derive(Debug, Default)]
+pub struct SyncReport {
+    pub shows: Vec<ShowId>,
+    pub people: Vec<PersonId>,
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Kind {
+    Show,
+    Person,
+}
+
+impl Kind {
+    fn name(self) -> &'static str {
+        match self {
+            Kind::Show => "shows",
+            Kind::Person => "people",
+        }
+    }
+
+    fn page_size(self) -> usize {
+        match self {
+            Kind::Show => SHOW_INDEX_PAGE_SIZE,
+            Kind::Person => PERSON_INDEX_PAGE_SIZE,
+        }
+    }
+
+    /// Path of the record with `id`, with everything the mirror stores about it.
+    fn record_path(self, id: usize) -> String {
+        match self {
+            Kind::Show => with_query(
+                &format!("/shows/{}", id),
+                embed::query(&(Episodes, Seasons, Cast)),
+            ),
+            Kind::Person => format!("/people/{}", id),
+        }
+    }
+}
+
+/// Progress of the mirror, saved as `state.json`.
+#[derive(Serialize, Deserialize, Debug, Default)]
+struct State {
+    /// Unix timestamp from which on updates still have to be looked up. Set when the crawl starts, so records changed during the crawl are refetched by the first sync.
+    synced_at: Option<i64>,
+    shows: Progress,
+    people: Progress,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+struct Progress {
+    /// Lowest ID of the index not stored yet.
+    next: usize,
+    crawled: bool,
+    /// Changed records not refetched yet.
+    pending: BTreeSet<usize>,
+}
+
+impl State {
+    fn progress(&mut self, kind: Kind) -> &mut Progress {
+        match kind {
+            Kind::Show => &mut self.shows,
+            Kind::Person => &mut self.people,
+        }
+    }
+}
+
+impl Mirror {
+    /// Open the mirror in `dir`, creating it if needed and picking up the progress saved there.
+    pub fn open(client: TvMazeClient, dir: impl Into<PathBuf>) -> Result<Self> {
+        let dir = dir.into();
+        fs::create_dir_all(dir.join(Kind::Show.name()))?;
+        fs::create_dir_all(dir.join(Kind::Person.name()))?;
+
+        let state = match fs::read_to_string(dir.join("state.json")) {
+            Ok(state) => parse("state.json", &state)?,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => State::default(),
+            Err(e) => return Err(e.into()),
+        };
+
+        Ok(Self { client, dir, state })
+    }
+
+    /// Whether the initial crawl of both indexes is complete.
+    pub fn is_crawled(&self) -> bool {
+        self.state.shows.crawled && self.state.people.crawled
+    }
+
+    /// Time up to which changes are reflected in the mirror, or `None` before the first crawl.
+    pub fn synced_at(&self) -> Option<DateTime<Utc>> {
+        self.state
+            .synced_at
+            .and_then(|synced_at| DateTime::from_timestamp(synced_at, 0))
+    }
+
+    /// Store every show and person listed in the indexes, continuing an interrupted crawl. Does nothing once the crawl is complete.
+    pub async fn crawl(&mut self) -> Result<()> {
+        if self.state.synced_at.is_none() {
+            self.state.synced_at = Some(Utc::now().timestamp());
+            self.save()?;
+        }
+        self.crawl_index(Kind::Show).await?;
+        self.crawl_index(Kind::Person).await
+    }
+
+    async fn crawl_index(&mut self, kind: Kind) -> Result<()> {
+        if self.state.progress(kind).crawled {
+            return Ok(());
+        }
+
+        let client = &self.client;
+        let start = self.state.progress(kind).next;
+        let mut records = std::pin::pin!(index_stream(
+            start / kind.page_size(),
+            |page| {
+                let path = format!("/{}?page={}", kind.name(), page);
+                async move { client.get_notfoundable::<Vec<Value>>(&path).await }
+            },
+            move |record: &Value| record["id"].as_u64().is_some_and(|id| id as usize >= start),
+        ));
+
+        let mut stored = 0;
+        while let Some(record) = records.next().await {
+            let record = match record {
+                Ok(record) => record,
+                Err(e) => {
+                    self.save()?;
+                    return Err(e);
+                }
+            };
+            let id = record["id"].as_u64().expect("kept records have an ID") as usize;
+            self.write(kind, id, &record.to_string())?;
+
+            self.state.progress(kind).next = id + 1;
+            stored += 1;
+            if stored % CHECKPOINT == 0 {
+                self.save()?;
+            }
+        }
+
+        self.state.progress(kind).crawled = true;
+        self.save()
+    }
+
+    /// Refetch every show and person that changed since the last sync, crawling first if that is not complete. Shows and people that no longer exist are removed. An interrupted sync is continued, and the stale entries of a cache configured on the client are evicted first.
+    pub async fn sync(&mut self) -> Result<SyncReport> {
+        self.crawl().await?;
+
+        let since = self.state.synced_at.unwrap_or_default();
+        let now = Utc::now().timestamp();
+        // One download of the updates serves both the cache and the mirror.
+        let oldest = self
+            .client
+            .oldest_update_candidate()
+            .map_or(since, |oldest| since.min(oldest as i64));
+        let window = covering_window((now - oldest).max(0) as u64);
+
+        let shows = self.client.show_updates(window).await?;
+        let people = self.client.person_updates(window).await?;
+        self.client.evict_with(&shows, &people);
+        let changed = |updated: &DateTime<Utc>| updated.timestamp() >= since;
+        self.state.shows.pending.extend(
+            shows
+                .into_iter()
+                .filter(|(_, updated)| changed(updated))
+                .map(|(id, _)| id.0),
+        );
+        self.state.people.pending.extend(
+            people
+                .into_iter()
+                .filter(|(_, updated)| changed(updated))
+                .map(|(id, _)| id.0),
+        );
+        self.state.synced_at = Some(now);
+        self.save()?;
+
+        Ok(SyncReport {
+            shows: self
+                .refetch(Kind::Show)
+                .await?
+                .into_iter()
+                .map(ShowId)
+                .collect(),
+            people: self
+                .refetch(Kind::Person)
+                .await?
+                .into_iter()
+                .map(PersonId)
+                .collect(),
+        })
+    }
+
+    /// Refetch the pending records of `kind`, returning their IDs.
+    async fn refetch(&mut self, kind: Kind) -> Result<Vec<usize>> {
+        let mut refetched = Vec::new();
+        while let Some(&id) = self.state.progress(kind).pending.first() {
+            let path = kind.record_path(id);
+            match self.client.fetch(&path).await? {
+                Some(body) => {
+                    // Make sure the record can be read back before storing it.
+                    match kind {
+                        Kind::Show => parse::<MirroredShow>(&path, &body).map(drop)?,
+                        Kind::Person => parse::<Person>(&path, &body).map(drop)?,
+                    }
+                    self.write(kind, id, &body)?;
+                }
+                None => self.remove(kind, id)?,
+            }
+
+            self.state.progress(kind).pending.remove(&id);
+            self.save()?;
+            refetched.push(id);
+        }
+        Ok(refetched)
+    }
+
+    /// The stored show, without its episodes, seasons and cast.
+    pub fn show(&self, id: ShowId) -> Result<Option<Show>> {
+        self.read(Kind::Show, id.0)
+    }
+
+    /// The stored show with its episodes, seasons and cast, or `None` if it was not refetched by a sync since the crawl.
+    pub fn show_details(&self, id: ShowId) -> Result<Option<MirroredShow>> {
+        #[derive(Deserialize)]
+        struct Details {
+            _embedded: Option<IgnoredAny>,
+        }
+
+        let Some(details) = self.read::<Details>(Kind::Show, id.0)? else {
+            return Ok(None);
+        };
+        match details._embedded {
+            Some(_) => self.read(Kind::Show, id.0),
+            None => Ok(None),
+        }
+    }
+
+    /// The stored person.
+    pub fn person(&self, id: PersonId) -> Result<Option<Person>> {
+        self.read(Kind::Person, id.0)
+    }
+
+    /// IDs of all stored shows, in ascending order.
+    pub fn show_ids(&self) -> Result<Vec<ShowId>> {
+        Ok(self.ids(Kind::Show)?.into_iter().map(ShowId).collect())
+    }
+
+    /// IDs of all stored people, in ascending order.
+    pub fn person_ids(&self) -> Result<Vec<PersonId>> {
+        Ok(self.ids(Kind::Person)?.into_iter().map(PersonId).collect())
+    }
+
+    fn path(&self, kind: Kind, id: usize) -> PathBuf {
+        self.dir.join(kind.name()).join(format!("{}.json", id))
+    }
+
+    fn read<T: serde::de::DeserializeOwned>(&self, kind: Kind, id: usize) -> Result<Option<T>> {
+        let path = self.path(kind, id);
+        match fs::read_to_string(&path) {
+            Ok(record) => parse(&path.to_string_lossy(), &record).map(Some),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn write(&self, kind: Kind, id: usize, record: &str) -> Result<()> {
+        Ok(write_atomic(&self.path(kind, id), record)?)
+    }
+
+    fn remove(&self, kind: Kind, id: usize) -> Result<()> {
+        match fs::remove_file(self.path(kind, id)) {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        }
+    }
+
+    fn ids(&self, kind: Kind) -> Result<Vec<usize>> {
+        let mut ids = Vec::new();
+        for file in fs::read_dir(self.dir.join(kind.name()))? {
+            let path = file?.path();
+            if path.extension().is_some_and(|ext| ext == "json") {
+                if let Some(id) = path
+                    .file_stem()
+                    .and_then(|stem| stem.to_str()?.parse().ok())
+                {
+                    ids.push(id);
+                }
+            }
+        }
+        ids.sort_unstable();
+        Ok(ids)
+    }
+
+    fn save(&self) -> Result<()> {
+        let state = serde_json::to_string(&self.state).expect("mirror state is serializable");
+        Ok(write_atomic(&self.dir.join("state.json"), &state)?)
+    }
+}
+
+/// Write to a temporary file first, so a crash never leaves a partial file behind.
+fn write_atomic(path: &Path, content: &str) -> io::Result<()> {
+    let tmp = path.with_extension("tmp");
+    fs::write(&tmp, content)?;
+    fs::rename(tmp, path)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{path::PathBuf, sync::Arc};
+
+    use chrono::Utc;
+    use reqwest::StatusCode;
+
+    use super::{Kind, Mirror};
+    use crate::{
+        cache::{Cache, CacheConfig, MemoryCache},
+        models::{PersonId, ShowId},
+        tests::{index_page, memory_client, payload},
+        transport::{path_and_query, MemoryTransport, Response},
+        TvMazeClient,
+    };
+
+    fn dir(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("tvmaze-mirror-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+
+    /// Shows 1, 2, 3 and 250 and people 1 and 2 in the indexes.
+    fn indexes() -> MemoryTransport {
+        MemoryTransport::new()
+            .with_json("/shows?page=0", index_page("/shows/1", [1, 2, 3]))
+            .with_json("/shows?page=1", index_page("/shows/1", [250]))
+            .with_json("/people?page=0", index_page("/people/1", [1, 2]))
+    }
+
+    /// An update endpoint response listing the given IDs with their update timestamps.
+    fn updates(updates: &[(usize, i64)]) -> String {
+        let updates: serde_json::Map<_, _> = updates
+            .iter()
+            .map(|(id, updated)| (id.to_string(), (*updated).into()))
+            .collect();
+        serde_json::to_string(&updates).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_crawl() {
+        let dir = dir("crawl");
+        let mut mirror = Mirror::open(memory_client(Arc::new(indexes())), &dir).unwrap();
+        assert!(mirror.synced_at().is_none());
+
+        mirror.crawl().await.unwrap();
+        assert!(mirror.is_crawled());
+        assert!(mirror.synced_at().is_some());
+        assert_eq!(mirror.show_ids().unwrap(), [1, 2, 3, 250].map(ShowId));
+        assert_eq!(mirror.person_ids().unwrap(), [1, 2].map(PersonId));
+        assert_eq!(
+            mirror.show(ShowId(250)).unwrap().unwrap().name,
+            "Under the Dome"
+        );
+        assert!(mirror.show_details(ShowId(1)).unwrap().is_none());
+        assert!(mirror.person(PersonId(3)).unwrap().is_none());
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_crawl_resumes() {
+        let dir = dir("resume");
+        let failing = MemoryTransport::new()
+            .with_json("/shows?page=0", index_page("/shows/1", [1, 2, 3]))
+            .with_response("/shows?page=1", Response::new(StatusCode::BAD_REQUEST, ""));
+        let mut mirror = Mirror::open(memory_client(Arc::new(failing)), &dir).unwrap();
+        assert!(mirror.crawl().await.is_err());
+        assert_eq!(mirror.show_ids().unwrap(), [1, 2, 3].map(ShowId));
+
+        // Shows stored before the failure are not stored again.
+        let page = index_page("/shows/1", [1, 2, 3]).replace("Under the Dome", "Renamed");
+        let transport = MemoryTransport::new()
+            .with_json("/shows?page=0", page)
+            .with_json("/shows?page=1", index_page("/shows/1", [250]))
+            .with_json("/people?page=0", index_page("/people/1", [1, 2]));
+        let mut mirror = Mirror::open(memory_client(Arc::new(transport)), &dir).unwrap();
+        assert!(!mirror.is_crawled());
+        mirror.crawl().await.unwrap();
+        assert!(mirror.is_crawled());
+        assert_eq!(mirror.show_ids().unwrap(), [1, 2, 3, 250].map(ShowId));
+        assert_eq!(
+            mirror.show(ShowId(1)).unwrap().unwrap().name,
+            "Under the Dome"
+        );
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_sync() {
+        let dir = dir("sync");
+        let transport = Arc::new(indexes());
+        let mut mirror = Mirror::open(memory_client(transport.clone()), &dir).unwrap();
+        mirror.crawl().await.unwrap();
+
+        // Show 1 is deleted and show 2993 added after the crawl, while show 2 changed before it.
+        let (changed, unchanged) = (Utc::now().timestamp(), Utc::now().timestamp() - 3600);
+        let transport = Arc::new(
+            indexes()
+                .with_json(
+                    "/updates/shows?since=day",
+                    updates(&[(1, changed), (2, unchanged), (2993, changed)]),
+                )
+                .with_json("/updates/shows?since=day", updates(&[]))
+                .with_json("/updates/people?since=day", updates(&[(25703, changed)]))
+                .with_json("/updates/people?since=day", updates(&[]))
+                .with_response(
+                    &Kind::Show.record_path(1),
+                    Response::new(StatusCode::NOT_FOUND, r#"{"name": "Not Found"}"#),
+                )
+                .with_json(
+                    &Kind::Show.record_path(2993),
+                    payload(&Kind::Show.record_path(2993)).unwrap()["body"].to_string(),
+                )
+                // The first attempt at the person fails, interrupting the sync.
+                .with_response("/people/25703", Response::new(StatusCode::BAD_REQUEST, ""))
+                .with_json(
+                    "/people/25703",
+                    payload("/people/25703").unwrap()["body"].to_string(),
+                ),
+        );
+
+        let mut mirror = Mirror::open(memory_client(transport.clone()), &dir).unwrap();
+        assert!(mirror.sync().await.is_err());
+        assert!(mirror.show(ShowId(1)).unwrap().is_none());
+        assert!(mirror.show_details(ShowId(2)).unwrap().is_none());
+        assert!(mirror.show_details(ShowId(2993)).unwrap().is_some());
+        assert!(mirror.person(PersonId(25703)).unwrap().is_none());
+
+        // The person is still refetched although no update lists it anymore.
+        let mut mirror = Mirror::open(memory_client(transport), &dir).unwrap();
+        let report = mirror.sync().await.unwrap();
+        assert!(report.shows.is_empty());
+        assert_eq!(report.people, [PersonId(25703)]);
+
+        let show = mirror.show_details(ShowId(2993)).unwrap().unwrap();
+        assert_eq!(show.name, "Stranger Things");
+        let (episodes, seasons, cast) = &*show._embedded;
+        assert!(!episodes.is_empty());
+        assert_eq!(seasons.len(), 4);
+        assert_eq!(cast[0].person.name, "Millie Bobby Brown");
+        assert_eq!(
+            mirror.person(PersonId(25703)).unwrap().unwrap().name,
+            "Millie Bobby Brown"
+        );
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_sync_with_cache() {
+        let dir = dir("sync-cache");
+        let updated = Utc::now().timestamp() + 100;
+        let transport = Arc::new(
+            indexes()
+                .with_json(
+                    "/updates/shows?since=day",
+                    updates(&[(1, updated), (2993, updated)]),
+                )
+                .with_json("/updates/people?since=day", updates(&[]))
+                .with_json(
+                    &Kind::Show.record_path(2993),
+                    payload(&Kind::Show.record_path(2993)).unwrap()["body"].to_string(),
+                ),
+        );
+        let cache = Arc::new(MemoryCache::new(100));
+        let client = TvMazeClient::builder()
+            .transport(transport.clone())
+            .rate_limit(None)
+            .cache(CacheConfig::new(cache.clone()))
+            .build()
+            .unwrap();
+        let mut mirror = Mirror::open(client.clone(), &dir).unwrap();
+        mirror.crawl().await.unwrap();
+        assert!(cache.get(&client.url("/shows?page=0")).is_some());
+
+        // The updates are downloaded once for both the cache and the mirror.
+        let report = mirror.sync().await.unwrap();
+        assert_eq!(report.shows, [ShowId(1), ShowId(2993)]);
+        assert!(cache.get(&client.url("/shows?page=0")).is_none());
+        assert!(cache.get(&client.url("/shows?page=1")).is_some());
+        let update_requests = transport
+            .requests()
+            .iter()
+            .filter(|request| path_and_query(&request.url).starts_with("/updates/"))
+            .count();
+        assert_eq!(update_requests, 2);
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
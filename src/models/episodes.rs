@@ -1,12 +1,15 @@
 use serde::Deserialize;
 
+use crate::embed::{Embedded, EpisodeEmbed};
+
 use super::{
     time::{TVMazeDate, TVMazeDateTime, TVMazeTime},
-    Image, Links, Rating,
+    CastMember, CrewMember, EpisodeLinks, Image, Rating, Show,
 };
 
 #[derive(Deserialize, Debug)]
-pub struct Episode {
+#[serde(bound(deserialize = ""))]
+pub struct Episode<E: EpisodeEmbed = ()> {
     pub id: usize,
     pub url: String,
     pub name: String,
@@ -20,5 +23,52 @@ pub struct Episode {
     pub rating: Rating,
     pub image: Option<Image>,
     pub summary: Option<String>,
-    pub _links: Links,
+    pub _links: EpisodeLinks,
+    pub _embedded: Embedded<E>,
+}
+
+/// Guest cast and crew of a single episode.
+#[derive(Debug)]
+pub struct EpisodeGuests {
+    pub episode: Episode,
+    pub cast: Vec<CastMember>,
+    pub crew: Vec<CrewMember>,
+}
+
+/// An episode in a schedule, together with its show.
+#[derive(Deserialize, Debug)]
+#[serde(try_from = "RawScheduledEpisode")]
+pub struct ScheduledEpisode {
+    pub episode: Episode,
+    pub show: Show,
+}
+
+/// The regular schedule includes the show as a field of the episode, while the web and full schedules embed it.
+#[derive(Deserialize)]
+struct RawScheduledEpisode {
+    #[serde(flatten)]
+    episode: Episode,
+    show: Option<Show>,
+    _embedded: Option<ScheduleEmbedded>,
+}
+
+#[derive(Deserialize)]
+struct ScheduleEmbedded {
+    show: Show,
+}
+
+impl TryFrom<RawScheduledEpisode> for ScheduledEpisode {
+    type Error = &'static str;
+
+    fn try_from(raw: RawScheduledEpisode) -> Result<Self, Self::Error> {
+        let show = raw
+            .show
+            .or(raw._embedded.map(|embedded| embedded.show))
+            .ok_or("scheduled episode without a show")?;
+
+        Ok(ScheduledEpisode {
+            episode: raw.episode,
+            show,
+        })
+    }
 }
@@ -0,0 +1,49 @@
+use std::fmt::Display;
+
+use serde::Deserialize;
+
+macro_rules! id_type {
+    ($(#[$attr:meta])* $name:ident) => {
+        $(#[$attr])*
+        #[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+        #[serde(transparent)]
+        pub struct $name(pub usize);
+
+        impl Display for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                self.0.fmt(f)
+            }
+        }
+
+        impl From<usize> for $name {
+            fn from(id: usize) -> Self {
+                $name(id)
+            }
+        }
+    };
+}
+
+id_type!(
+    /// ID of a show.
+    ShowId
+);
+id_type!(
+    /// ID of a person.
+    PersonId
+);
+id_type!(
+    /// ID of a network.
+    NetworkId
+);
+id_type!(
+    /// ID of a web channel. Web channels are numbered separately from networks.
+    WebChannelId
+);
+id_type!(
+    /// ID of an episode.
+    EpisodeId
+);
+id_type!(
+    /// ID of a user's tag.
+    TagId
+);
@@ -0,0 +1,118 @@
+use std::{fmt::Debug, marker::PhantomData};
+
+use serde::{de::DeserializeOwned, Deserialize};
+
+use crate::{transport::path_and_query, Result, TvMazeClient};
+
+use super::{AlternateEpisode, AlternateList, Character, Episode, Person, Season, Show};
+
+/// A link to a resource of type `T`, which can be followed with [`Link::fetch`].
+#[derive(Deserialize)]
+#[serde(bound(deserialize = ""))]
+pub struct Link<T> {
+    pub href: String,
+    /// Name of the target, only present on links to other resources.
+    pub name: Option<String>,
+    #[serde(skip)]
+    target: PhantomData<fn() -> T>,
+}
+
+impl<T: DeserializeOwned> Link<T> {
+    /// Retrieve the linked resource. The link is resolved against the client's base URL, not the host in `href`.
+    pub async fn fetch(&self, client: &TvMazeClient) -> Result<Option<T>> {
+        client.get_notfoundable(&path_and_query(&self.href)).await
+    }
+}
+
+impl<T> Debug for Link<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Link")
+            .field("href", &self.href)
+            .field("name", &self.name)
+            .finish()
+    }
+}
+
+#[derive(Deserialize, Debug)]
+pub struct ShowLinks {
+    #[serde(rename = "self")]
+    pub _self: Link<Show>,
+    pub previousepisode: Option<Link<Episode>>,
+    pub nextepisode: Option<Link<Episode>>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct EpisodeLinks {
+    #[serde(rename = "self")]
+    pub _self: Link<Episode>,
+    pub show: Option<Link<Show>>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct SeasonLinks {
+    #[serde(rename = "self")]
+    pub _self: Link<Season>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct AlternateListLinks {
+    #[serde(rename = "self")]
+    pub _self: Link<AlternateList>,
+    pub show: Option<Link<Show>>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct AlternateEpisodeLinks {
+    #[serde(rename = "self")]
+    pub _self: Link<AlternateEpisode>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct PersonLinks {
+    #[serde(rename = "self")]
+    pub _self: Link<Person>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct CharacterLinks {
+    #[serde(rename = "self")]
+    pub _self: Link<Character>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct CastCreditLinks {
+    pub show: Link<Show>,
+    pub character: Link<Character>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct CrewCreditLinks {
+    pub show: Link<Show>,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::fixture_client;
+
+    #[tokio::test]
+    async fn test_follow_credit_links() {
+        let client = fixture_client();
+        let credits = client.person_cast_credits(1, ()).await.unwrap().unwrap();
+        let links = &credits[0]._links;
+
+        let show = links.show.fetch(&client).await.unwrap().unwrap();
+        assert_eq!(show.name, links.show.name.as_deref().unwrap());
+
+        let character = links.character.fetch(&client).await.unwrap().unwrap();
+        assert_eq!(character.name, "Dale \"Barbie\" Barbara");
+        assert_eq!(character._links._self.href, links.character.href);
+    }
+
+    #[tokio::test]
+    async fn test_follow_self_link() {
+        let client = fixture_client();
+        let show = client.show(1, ()).await.unwrap().unwrap();
+        let again = show._links._self.fetch(&client).await.unwrap().unwrap();
+        assert_eq!(again.id, show.id);
+    }
+}
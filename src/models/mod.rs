@@ -2,6 +2,10 @@ use serde::Deserialize;
 
 mod episodes;
 pub use episodes::*;
+mod ids;
+pub use ids::*;
+mod links;
+pub use links::*;
 mod people;
 pub use people::*;
 mod shows;
@@ -9,6 +13,10 @@ pub use shows::*;
 mod search;
 pub use search::*;
 mod time;
+#[cfg(feature = "user-api")]
+mod user;
+#[cfg(feature = "user-api")]
+pub use user::*;
 
 #[derive(Deserialize, Debug)]
 pub struct Rating {
@@ -27,15 +35,3 @@ pub struct Image {
     pub medium: String,
     pub original: String,
 }
-
-#[derive(Deserialize, Debug)]
-pub struct Links {
-    #[serde(rename = "self")]
-    pub _self: Option<Hrefed>,
-    pub previousepisode: Option<Hrefed>,
-}
-
-#[derive(Deserialize, Debug)]
-pub struct Hrefed {
-    pub href: String,
-}
@@ -1,9 +1,15 @@
 use serde::Deserialize;
 
-use super::{time::TVMazeDate, Country, Image, Links};
+use crate::embed::{CreditEmbed, CrewCreditEmbed, Embedded, PersonEmbed};
+
+use super::{
+    time::TVMazeDate, CastCreditLinks, Character, Country, CrewCreditLinks, Image, PersonLinks,
+    Show,
+};
 
 #[derive(Deserialize, Debug)]
-pub struct Person {
+#[serde(bound(deserialize = ""))]
+pub struct Person<E: PersonEmbed = ()> {
     pub id: usize,
     pub url: String,
     pub name: String,
@@ -13,19 +19,34 @@ pub struct Person {
     pub gender: Option<String>,
     pub image: Option<Image>,
     pub updated: usize,
-    pub _links: Links,
+    pub _links: PersonLinks,
+    pub _embedded: Embedded<E>,
 }
 
 #[derive(Deserialize, Debug)]
-pub struct CrewCredit {
+#[serde(bound(deserialize = ""))]
+pub struct CrewCredit<E: CrewCreditEmbed = ()> {
     pub r#type: String,
-    pub _links: Links,
+    pub _links: CrewCreditLinks,
+    pub _embedded: Embedded<E>,
 }
 
 #[derive(Deserialize, Debug)]
-pub struct CastCredit {
+#[serde(bound(deserialize = ""))]
+pub struct CastCredit<E: CreditEmbed = ()> {
     #[serde(rename = "self")]
     pub _self: bool,
     pub voice: bool,
-    pub _links: Links,
+    pub _links: CastCreditLinks,
+    pub _embedded: Embedded<E>,
+}
+
+/// A cast credit resolved into its show and character.
+#[derive(Debug)]
+pub struct FilmographyEntry {
+    pub show: Show,
+    pub character: Character,
+    pub voice: bool,
+    /// Whether the person plays themselves.
+    pub _self: bool,
 }
@@ -1,6 +1,9 @@
 use serde::Deserialize;
 
-use super::{people::Person, shows::Show};
+use super::{
+    people::Person,
+    shows::{Network, Show, WebChannel},
+};
 
 #[derive(Deserialize, Debug)]
 pub struct SearchResult {
@@ -13,3 +16,16 @@ pub struct SearchResultPerson {
     pub score: f32,
     pub person: Person,
 }
+
+#[derive(Deserialize, Debug)]
+pub struct SearchResultNetwork {
+    pub score: f32,
+    pub network: Network,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct SearchResultWebChannel {
+    pub score: f32,
+    #[serde(rename = "webchannel")]
+    pub web_channel: WebChannel,
+}
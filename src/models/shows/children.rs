@@ -1,8 +1,6 @@
 use serde::Deserialize;
 
-use crate::models::{Links, Image, Episode, time::TVMazeTime, Country};
-
-use super::CastMember;
+use crate::models::{CharacterLinks, Image, time::TVMazeTime, Country};
 
 #[derive(Deserialize, Debug)]
 pub struct Resolutions {
@@ -17,6 +15,7 @@ pub struct Resolution {
     pub height: usize,
 }
 
+/// A broadcast network, like a TV channel.
 #[derive(Deserialize, Debug)]
 pub struct Network {
     pub id: usize,
@@ -26,6 +25,16 @@ pub struct Network {
     pub official_site: Option<String>,
 }
 
+/// An online streaming service. Unlike networks, web channels without a country are available globally.
+#[derive(Deserialize, Debug)]
+pub struct WebChannel {
+    pub id: usize,
+    pub name: String,
+    pub country: Option<Country>,
+    #[serde(rename = "officialSite")]
+    pub official_site: Option<String>,
+}
+
 #[derive(Deserialize, Debug)]
 pub struct Externals {
     pub tvrage: Option<usize>,
@@ -39,18 +48,11 @@ pub struct Schedule {
     pub days: Vec<String>,
 }
 
-#[derive(Deserialize, Debug)]
-pub struct Embedded {
-    pub episodes: Option<Vec<Episode>>,
-    pub cast: Option<Vec<CastMember>>,
-    pub nextepisode: Option<Episode>,
-}
-
 #[derive(Deserialize, Debug)]
 pub struct Character {
     pub id: usize,
     pub url: String,
     pub name: String,
     pub image: Option<Image>,
-    pub _links: Links,
+    pub _links: CharacterLinks,
 }
\ No newline at end of file
@@ -1,9 +1,12 @@
 use serde::Deserialize;
 
+use crate::embed::{
+    AlternateEpisodeEmbed, AlternateListEmbed, Embedded, Episodes, SeasonEmbed, ShowEmbed,
+};
+
 use super::{
-    people::Person,
-    time::{TVMazeDate},
-    Country, Image, Links, Rating,
+    people::Person, time::TVMazeDate, AlternateEpisodeLinks, AlternateListLinks, Country, Image,
+    Rating, SeasonLinks, ShowLinks,
 };
 
 mod children;
@@ -12,7 +15,8 @@ mod enums;
 pub use enums::*;
 
 #[derive(Deserialize, Debug)]
-pub struct Show {
+#[serde(bound(deserialize = ""))]
+pub struct Show<E: ShowEmbed = ()> {
     pub id: usize,
     pub url: String,
     pub name: String,
@@ -32,19 +36,20 @@ pub struct Show {
     pub weight: usize,
     pub network: Option<Network>,
     #[serde(rename = "webChannel")]
-    pub web_channel: Option<Network>,
+    pub web_channel: Option<WebChannel>,
     #[serde(rename = "dvdCountry")]
     pub dvd_country: Option<Country>,
     pub externals: Externals,
     pub image: Option<Image>,
     pub summary: Option<String>,
     pub updated: u64,
-    pub _links: Links,
-    pub _embedded: Option<Embedded>,
+    pub _links: ShowLinks,
+    pub _embedded: Embedded<E>,
 }
 
 #[derive(Deserialize, Debug)]
-pub struct AlternateList {
+#[serde(bound(deserialize = ""))]
+pub struct AlternateList<E: AlternateListEmbed = ()> {
     pub id: usize,
     pub url: String,
     pub dvd_release: bool,
@@ -56,12 +61,26 @@ pub struct AlternateList {
     pub language: Option<String>,
     pub network: Option<Network>,
     #[serde(rename = "webChannel")]
-    pub web_channel: Option<Network>,
-    pub _links: Links,
+    pub web_channel: Option<WebChannel>,
+    pub _links: AlternateListLinks,
+    pub _embedded: Embedded<E>,
 }
 
+/// An episode's position in an alternate list. Usually stands for a single episode, but can combine several, e.g. a two-part episode released as one.
 #[derive(Deserialize, Debug)]
-pub struct Season {
+#[serde(bound(deserialize = ""))]
+pub struct AlternateEpisode<E: AlternateEpisodeEmbed = ()> {
+    pub id: usize,
+    pub url: String,
+    pub season: usize,
+    pub number: Option<usize>,
+    pub _links: AlternateEpisodeLinks,
+    pub _embedded: Embedded<E>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(bound(deserialize = ""))]
+pub struct Season<E: SeasonEmbed = ()> {
     pub id: usize,
     pub url: String,
     pub number: usize,
@@ -74,12 +93,16 @@ pub struct Season {
     pub end_date: Option<TVMazeDate>,
     pub network: Option<Network>,
     #[serde(rename = "webChannel")]
-    pub web_channel: Option<Network>,
+    pub web_channel: Option<WebChannel>,
     pub image: Option<Image>,
     pub summary: Option<String>,
-    pub _links: Links,
+    pub _links: SeasonLinks,
+    pub _embedded: Embedded<E>,
 }
 
+/// A season with all its episodes, specials included.
+pub type SeasonWithEpisodes = Season<Episodes>;
+
 #[derive(Deserialize, Debug)]
 pub struct Aka {
     pub name: String,
@@ -96,6 +119,7 @@ pub struct ShowImage {
 
 #[derive(Deserialize, Debug)]
 pub struct CrewMember {
+    #[serde(alias = "guestCrewType")]
     pub r#type: String,
     pub person: Person,
 }
@@ -107,4 +131,4 @@ pub struct CastMember {
     #[serde(rename = "self")]
     pub _self: bool,
     pub voice: bool,
-}
\ No newline at end of file
+}
@@ -19,12 +19,17 @@ use serde::Deserialize;
 // }
 
 #[derive(Deserialize, Debug)]
-#[serde(from = "String")]
+#[serde(try_from = "String")]
 pub struct TVMazeDateTime(pub chrono::DateTime<FixedOffset>);
 
-impl From<String> for TVMazeDateTime {
-    fn from(s: String) -> Self {
-        TVMazeDateTime(DateTime::parse_from_str(&s, "%Y-%m-%dT%H:%M:%S%:z").unwrap())
+impl TryFrom<String> for TVMazeDateTime {
+    type Error = chrono::ParseError;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        Ok(TVMazeDateTime(DateTime::parse_from_str(
+            &s,
+            "%Y-%m-%dT%H:%M:%S%:z",
+        )?))
     }
 }
 
@@ -39,11 +44,13 @@ impl From<String> for TVMazeTime {
 }
 
 #[derive(Deserialize, Debug)]
-#[serde(from = "String")]
+#[serde(try_from = "String")]
 pub struct TVMazeDate(pub chrono::NaiveDate);
 
-impl From<String> for TVMazeDate {
-    fn from(s: String) -> Self {
-        TVMazeDate(NaiveDate::parse_from_str(&s, "%Y-%m-%d").unwrap())
+impl TryFrom<String> for TVMazeDate {
+    type Error = chrono::ParseError;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        Ok(TVMazeDate(NaiveDate::parse_from_str(&s, "%Y-%m-%d")?))
     }
 }
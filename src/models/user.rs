@@ -0,0 +1,105 @@
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+use crate::embed::{
+    Embedded, EpisodeVoteEmbed, FollowedNetworkEmbed, FollowedPersonEmbed, FollowedShowEmbed,
+    FollowedWebChannelEmbed, MarkingEmbed, ShowVoteEmbed,
+};
+
+use super::{EpisodeId, NetworkId, PersonId, ShowId, TagId, WebChannelId};
+
+#[derive(Deserialize, Debug)]
+#[serde(bound(deserialize = ""))]
+pub struct FollowedShow<E: FollowedShowEmbed = ()> {
+    pub show_id: ShowId,
+    pub _embedded: Embedded<E>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(bound(deserialize = ""))]
+pub struct FollowedPerson<E: FollowedPersonEmbed = ()> {
+    pub person_id: PersonId,
+    pub _embedded: Embedded<E>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(bound(deserialize = ""))]
+pub struct FollowedNetwork<E: FollowedNetworkEmbed = ()> {
+    pub network_id: NetworkId,
+    pub _embedded: Embedded<E>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(bound(deserialize = ""))]
+pub struct FollowedWebChannel<E: FollowedWebChannelEmbed = ()> {
+    #[serde(rename = "webchannel_id")]
+    pub web_channel_id: WebChannelId,
+    pub _embedded: Embedded<E>,
+}
+
+/// How an episode is marked.
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(try_from = "u8")]
+pub enum MarkType {
+    Watched = 0,
+    Acquired = 1,
+    Skipped = 2,
+}
+
+impl TryFrom<u8> for MarkType {
+    type Error = &'static str;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(MarkType::Watched),
+            1 => Ok(MarkType::Acquired),
+            2 => Ok(MarkType::Skipped),
+            _ => Err("unknown mark type"),
+        }
+    }
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(bound(deserialize = ""))]
+pub struct EpisodeMarking<E: MarkingEmbed = ()> {
+    pub episode_id: EpisodeId,
+    #[serde(with = "chrono::serde::ts_seconds")]
+    pub marked_at: DateTime<Utc>,
+    pub r#type: MarkType,
+    pub _embedded: Embedded<E>,
+}
+
+/// A vote on a show, from 1 to 10.
+#[derive(Deserialize, Debug)]
+#[serde(bound(deserialize = ""))]
+pub struct ShowVote<E: ShowVoteEmbed = ()> {
+    pub show_id: ShowId,
+    #[serde(with = "chrono::serde::ts_seconds")]
+    pub voted_at: DateTime<Utc>,
+    pub vote: u8,
+    pub _embedded: Embedded<E>,
+}
+
+/// A vote on an episode, from 1 to 10.
+#[derive(Deserialize, Debug)]
+#[serde(bound(deserialize = ""))]
+pub struct EpisodeVote<E: EpisodeVoteEmbed = ()> {
+    pub episode_id: EpisodeId,
+    #[serde(with = "chrono::serde::ts_seconds")]
+    pub voted_at: DateTime<Utc>,
+    pub vote: u8,
+    pub _embedded: Embedded<E>,
+}
+
+/// A tag the user organises shows with.
+#[derive(Deserialize, Debug, Clone)]
+pub struct Tag {
+    pub id: TagId,
+    pub name: String,
+}
+
+/// A show assigned to a tag.
+#[derive(Deserialize, Debug)]
+pub struct TaggedShow {
+    pub show_id: ShowId,
+}
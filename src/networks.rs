@@ -0,0 +1,89 @@
+use futures_util::TryStreamExt;
+
+use crate::{
+    models::{Network, Show, WebChannel},
+    Result, TvMazeClient,
+};
+
+impl TvMazeClient {
+    /// Retrieve all primary information for a given network.
+    pub async fn network(&self, id: usize) -> Result<Option<Network>> {
+        self.get_notfoundable(&format!("/networks/{}", id)).await
+    }
+
+    /// Retrieve all primary information for a given web channel.
+    pub async fn web_channel(&self, id: usize) -> Result<Option<WebChannel>> {
+        self.get_notfoundable(&format!("/webchannels/{}", id)).await
+    }
+
+    /// All shows airing on a network. There is no endpoint for this, so the whole show index is walked, which takes a few hundred requests.
+    pub async fn network_shows(&self, network: usize) -> Result<Vec<Show>> {
+        self.index_shows(|show| show.network.as_ref().is_some_and(|n| n.id == network))
+            .await
+    }
+
+    /// All shows streaming on a web channel. Like [`TvMazeClient::network_shows`], this walks the whole show index.
+    pub async fn web_channel_shows(&self, web_channel: usize) -> Result<Vec<Show>> {
+        self.index_shows(|show| {
+            show.web_channel
+                .as_ref()
+                .is_some_and(|w| w.id == web_channel)
+        })
+        .await
+    }
+
+    async fn index_shows(&self, keep: impl Fn(&Show) -> bool) -> Result<Vec<Show>> {
+        self.show_index_stream(None)
+            .try_filter(|show| std::future::ready(keep(show)))
+            .try_collect()
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::{
+        models::Show,
+        tests::{fixture_client, index_page, memory_client, payload},
+        transport::MemoryTransport,
+    };
+
+    #[tokio::test]
+    async fn test_network() {
+        let client = fixture_client();
+        let network = client.network(2).await.unwrap().unwrap();
+        assert_eq!(network.name, "CBS");
+        assert_eq!(network.country.unwrap().code, "US");
+    }
+
+    #[tokio::test]
+    async fn test_web_channel() {
+        let client = fixture_client();
+        let web_channel = client.web_channel(1).await.unwrap().unwrap();
+        assert_eq!(web_channel.name, "Netflix");
+    }
+
+    #[tokio::test]
+    async fn test_network_shows() {
+        // The first page of the index from the fixtures, followed by a page with another CBS show and a Netflix show.
+        let mut page: Vec<serde_json::Value> =
+            serde_json::from_str(&index_page("/shows/1", [250, 251])).unwrap();
+        page[1]["network"] = serde_json::Value::Null;
+        page[1]["webChannel"] = payload("/webchannels/1").unwrap()["body"].take();
+        let transport = MemoryTransport::new()
+            .with_json(
+                "/shows?page=0",
+                payload("/shows?page=0").unwrap()["body"].to_string(),
+            )
+            .with_json("/shows?page=1", serde_json::to_string(&page).unwrap());
+        let client = memory_client(Arc::new(transport));
+        let ids = |shows: Vec<Show>| shows.into_iter().map(|show| show.id).collect::<Vec<_>>();
+
+        assert_eq!(ids(client.network_shows(2).await.unwrap()), [1, 2, 250]);
+        // Networks and web channels are numbered separately, NBC shares its ID with Netflix.
+        assert_eq!(ids(client.network_shows(1).await.unwrap()), [204]);
+        assert_eq!(ids(client.web_channel_shows(1).await.unwrap()), [251]);
+    }
+}
@@ -1,55 +1,223 @@
-use reqwest::StatusCode;
+use futures_util::Stream;
 
-use crate::models::{CastCredit, CrewCredit, Person};
+use crate::{
+    client::with_query,
+    embed::{self, Character, CreditEmbed, CrewCreditEmbed, PersonEmbed, Show},
+    index::index_stream,
+    models::{CastCredit, CrewCredit, FilmographyEntry, Person, PersonId},
+    Result, TvMazeClient,
+};
 
-/// Retrieve all primary information for a given person. This endpoint allows embedding of additional information. See the section embedding for more information.
-pub async fn person(id: usize) -> Result<Option<Person>, reqwest::Error> {
-    crate::notfoundable_endpoint!(format!("https://api.tvmaze.com/people/{}", id,))
-}
+/// Number of people covered by a page of the person index.
+pub(crate) const PERSON_INDEX_PAGE_SIZE: usize = 1000;
 
-/// Retrieve all (show-level) cast credits for a person. A cast credit is a combination of both a show and a character. By default, only a reference to each show and character will be returned. However, this endpoint supports embedding, which means full information for the shows and characters can be included.
-pub async fn person_cast_credits(id: usize) -> Result<Option<Vec<CastCredit>>, reqwest::Error> {
-    crate::notfoundable_endpoint!(format!("https://api.tvmaze.com/people/{}/castcredits", id,))
-}
+impl TvMazeClient {
+    /// Retrieve all primary information for a given person. This endpoint allows embedding of additional information. See the section embedding for more information.
+    pub async fn person<E: PersonEmbed>(&self, id: usize, embed: E) -> Result<Option<Person<E>>> {
+        self.get_notfoundable(&with_query(
+            &format!("/people/{}", id),
+            embed::query(&embed),
+        ))
+        .await
+    }
 
-/// Retrieve all (show-level) crew credits for a person. A crew credit is combination of both a show and a crew type. By default, only a reference to each show will be returned. However, this endpoint supports embedding, which means full information for the shows can be included.
-pub async fn person_crew_credits(id: usize) -> Result<Option<Vec<CrewCredit>>, reqwest::Error> {
-    crate::notfoundable_endpoint!(format!("https://api.tvmaze.com/people/{}/crewcredits", id,))
-}
+    /// Retrieve all (show-level) cast credits for a person. A cast credit is a combination of both a show and a character. By default, only a reference to each show and character will be returned. However, this endpoint supports embedding, which means full information for the shows and characters can be included.
+    pub async fn person_cast_credits<E: CreditEmbed>(
+        &self,
+        id: usize,
+        embed: E,
+    ) -> Result<Option<Vec<CastCredit<E>>>> {
+        self.get_notfoundable(&with_query(
+            &format!("/people/{}/castcredits", id),
+            embed::query(&embed),
+        ))
+        .await
+    }
 
-/// Like the show index but for people; please refer to the show index documentation. A maximum of 1000 results per page is returned.
-pub async fn person_index(page: usize) -> Result<Vec<Person>, reqwest::Error> {
-    Ok(serde_json::from_str(
-        &reqwest::get(format!("https://api.tvmaze.com/people?page={}", page,))
-            .await?
-            .text()
-            .await?,
-    )
-    .unwrap())
+    /// Retrieve all (show-level) crew credits for a person. A crew credit is combination of both a show and a crew type. By default, only a reference to each show will be returned. However, this endpoint supports embedding, which means full information for the shows can be included.
+    pub async fn person_crew_credits<E: CrewCreditEmbed>(
+        &self,
+        id: usize,
+        embed: E,
+    ) -> Result<Option<Vec<CrewCredit<E>>>> {
+        self.get_notfoundable(&with_query(
+            &format!("/people/{}/crewcredits", id),
+            embed::query(&embed),
+        ))
+        .await
+    }
+
+    /// All cast credits of a person with their shows and characters resolved, in one request. Entries are sorted by the show's premiere date, with unknown dates last.
+    pub async fn person_filmography(&self, id: usize) -> Result<Option<Vec<FilmographyEntry>>> {
+        let Some(credits) = self.person_cast_credits(id, (Show, Character)).await? else {
+            return Ok(None);
+        };
+
+        let mut entries: Vec<_> = credits
+            .into_iter()
+            .map(|credit| {
+                let (show, character) = credit._embedded.into_inner();
+                FilmographyEntry {
+                    show,
+                    character,
+                    voice: credit.voice,
+                    _self: credit._self,
+                }
+            })
+            .collect();
+        entries.sort_by_key(|entry| {
+            let premiered = entry.show.premiered.as_ref().map(|date| date.0);
+            (premiered.is_none(), premiered)
+        });
+
+        Ok(Some(entries))
+    }
+
+    /// Like the show index but for people; please refer to the show index documentation. A maximum of 1000 results per page is returned.
+    pub async fn person_index(&self, page: usize) -> Result<Option<Vec<Person>>> {
+        self.get_notfoundable(&format!("/people?page={}", page))
+            .await
+    }
+
+    /// Every person of the [person index](TvMazeClient::person_index), like [`TvMazeClient::show_index_stream`].
+    pub fn person_index_stream(
+        &self,
+        start: Option<PersonId>,
+    ) -> impl Stream<Item = Result<Person>> + '_ {
+        let start = start.map_or(0, |id| id.0);
+        index_stream(
+            start / PERSON_INDEX_PAGE_SIZE,
+            move |page| self.person_index(page),
+            move |person: &Person| person.id >= start,
+        )
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{person, person_cast_credits, person_crew_credits, person_index};
+    use std::sync::Arc;
+
+    use futures_util::StreamExt;
+
+    use crate::{
+        embed::{CastCredits, Character, CrewCredits, Show},
+        models::PersonId,
+        tests::{fixture_client, index_page, memory_client, payload},
+        transport::MemoryTransport,
+    };
 
     #[tokio::test]
     async fn test_person() {
-        person(1).await.unwrap();
+        let client = fixture_client();
+        client.person(1, ()).await.unwrap();
     }
 
     #[tokio::test]
     async fn test_person_cast_credits() {
-        person_cast_credits(1).await.unwrap();
+        let client = fixture_client();
+        client.person_cast_credits(1, ()).await.unwrap();
     }
 
     #[tokio::test]
     async fn test_person_crew_credits() {
-        person_crew_credits(1).await.unwrap();
+        let client = fixture_client();
+        client.person_crew_credits(1, ()).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_person_embed() {
+        let client = fixture_client();
+        let res = client
+            .person(1, (CastCredits, CrewCredits))
+            .await
+            .unwrap()
+            .unwrap();
+        let (castcredits, crewcredits) = res._embedded.into_inner();
+
+        assert_eq!(castcredits.len(), 2);
+        assert_eq!(crewcredits.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_person_cast_credits_embed() {
+        let client = fixture_client();
+        let credits = client
+            .person_cast_credits(1, (Show, Character))
+            .await
+            .unwrap()
+            .unwrap();
+        let (show, character) = &*credits[0]._embedded;
+
+        assert_eq!(show.name, "Under the Dome");
+        assert_eq!(character.name, "Dale \"Barbie\" Barbara");
+    }
+
+    #[tokio::test]
+    async fn test_person_crew_credits_embed() {
+        let client = fixture_client();
+        let credits = client.person_crew_credits(1, Show).await.unwrap().unwrap();
+
+        assert_eq!(credits[0]._embedded.id, 1);
+    }
+
+    #[tokio::test]
+    async fn test_person_filmography() {
+        // Start from the fixture credits and rearrange them so the sorting and flags are exercised.
+        let credits = payload("/people/1/castcredits?embed[]=show&embed[]=character").unwrap();
+        let (dome, bitten) = (&credits["body"][0], &credits["body"][1]);
+
+        let mut unaired = dome.clone();
+        unaired["self"] = true.into();
+        unaired["_embedded"]["show"]["name"] = "Unaired".into();
+        unaired["_embedded"]["show"]["premiered"] = serde_json::Value::Null;
+        let mut voiced = bitten.clone();
+        voiced["voice"] = true.into();
+        voiced["_embedded"]["show"]["name"] = "Voiced".into();
+        voiced["_embedded"]["show"]["premiered"] = "2001-09-17".into();
+        voiced["_embedded"]["character"]["name"] = "Narrator".into();
+        let credits = serde_json::json!([unaired, bitten, voiced, dome]);
+
+        let client = memory_client(Arc::new(MemoryTransport::new().with_json(
+            "/people/1/castcredits?embed[]=show&embed[]=character",
+            credits.to_string(),
+        )));
+        let entries = client.person_filmography(1).await.unwrap().unwrap();
+
+        assert_eq!(
+            entries
+                .iter()
+                .map(|entry| entry.show.name.as_str())
+                .collect::<Vec<_>>(),
+            ["Voiced", "Under the Dome", "Bitten", "Unaired"]
+        );
+        assert_eq!(entries[0].character.name, "Narrator");
+        assert!(entries[0].voice && !entries[0]._self);
+        assert!(!entries[1].voice && !entries[1]._self);
+        assert!(entries[3]._self && !entries[3].voice);
     }
 
     #[tokio::test]
     async fn test_person_index() {
-        let res = &person_index(0).await.unwrap()[0];
+        let client = fixture_client();
+        let res = &client.person_index(0).await.unwrap().unwrap()[0];
         assert!(res.id <= 250);
     }
+
+    #[tokio::test]
+    async fn test_person_index_stream() {
+        let transport = MemoryTransport::new()
+            .with_json("/people?page=0", index_page("/people/1", [1, 999]))
+            .with_json("/people?page=1", index_page("/people/1", [1000, 1500]));
+        let client = memory_client(Arc::new(transport));
+        let ids = |start| {
+            client
+                .person_index_stream(start)
+                .map(|person| person.unwrap().id)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(ids(None).await, [1, 999, 1000, 1500]);
+        assert_eq!(ids(Some(PersonId(1001))).await, [1500]);
+        assert!(client.person_index(2).await.unwrap().is_none());
+    }
 }
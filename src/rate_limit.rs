@@ -0,0 +1,157 @@
+use std::{
+    collections::hash_map::RandomState,
+    hash::{BuildHasher, Hasher},
+    time::Duration,
+};
+
+use tokio::{sync::Mutex, time::Instant};
+
+/// How many calls may be made in a given period. TVmaze allows at least 20 calls every 10 seconds per IP address.
+#[derive(Clone, Copy, Debug)]
+pub struct RateLimit {
+    /// Must be at least 1.
+    pub calls: u32,
+    pub per: Duration,
+}
+
+impl Default for RateLimit {
+    fn default() -> Self {
+        Self {
+            calls: 20,
+            per: Duration::from_secs(10),
+        }
+    }
+}
+
+/// How often and how long to wait before retrying a request that was rate limited or failed with a server error. The delay doubles with every attempt, up to `max_delay`, and is randomized to avoid many clients retrying in lockstep. A `Retry-After` longer than `max_delay` is not waited for; the request fails with [`crate::Error::RateLimited`] instead.
+#[derive(Clone, Copy, Debug)]
+pub struct RetryPolicy {
+    pub max_retries: u32,
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+}
+
+impl RetryPolicy {
+    /// Never retry.
+    pub const NONE: RetryPolicy = RetryPolicy {
+        max_retries: 0,
+        base_delay: Duration::ZERO,
+        max_delay: Duration::ZERO,
+    };
+
+    /// Delay before retry number `attempt`, counting from zero.
+    pub fn delay(&self, attempt: u32) -> Duration {
+        let delay = self
+            .base_delay
+            .saturating_mul(2u32.saturating_pow(attempt))
+            .min(self.max_delay);
+
+        // Pick a random point in the upper half of the delay.
+        let half = delay / 2;
+        half + half.mul_f64(jitter())
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(10),
+        }
+    }
+}
+
+/// Random number in `[0, 1)`, good enough for spreading out retries.
+fn jitter() -> f64 {
+    // Every `RandomState` is seeded differently, so hashing nothing is enough.
+    let hash = RandomState::new().build_hasher().finish();
+    (hash >> 11) as f64 / (1u64 << 53) as f64
+}
+
+/// Token bucket shared by all clones of a client.
+#[derive(Debug)]
+pub(crate) struct RateLimiter {
+    limit: RateLimit,
+    bucket: Mutex<Bucket>,
+}
+
+#[derive(Debug)]
+struct Bucket {
+    tokens: f64,
+    refilled: Instant,
+}
+
+impl RateLimiter {
+    pub(crate) fn new(limit: RateLimit) -> Self {
+        Self {
+            limit,
+            bucket: Mutex::new(Bucket {
+                tokens: limit.calls as f64,
+                refilled: Instant::now(),
+            }),
+        }
+    }
+
+    /// Wait until a call may be made. Waiters are served in order, since the lock is held while sleeping.
+    pub(crate) async fn acquire(&self) {
+        let capacity = self.limit.calls as f64;
+        let per_token = self.limit.per.as_secs_f64() / capacity;
+
+        let mut bucket = self.bucket.lock().await;
+
+        let now = Instant::now();
+        let refill = now.duration_since(bucket.refilled).as_secs_f64() / per_token;
+        bucket.tokens = (bucket.tokens + refill).min(capacity);
+        bucket.refilled = now;
+
+        if bucket.tokens < 1.0 {
+            let wait = Duration::from_secs_f64((1.0 - bucket.tokens) * per_token);
+            tokio::time::sleep(wait).await;
+            bucket.tokens = 1.0;
+            bucket.refilled = Instant::now();
+        }
+
+        bucket.tokens -= 1.0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use tokio::time::Instant;
+
+    use super::{RateLimit, RateLimiter, RetryPolicy};
+
+    #[tokio::test(start_paused = true)]
+    async fn test_rate_limiter() {
+        let limiter = RateLimiter::new(RateLimit {
+            calls: 2,
+            per: Duration::from_secs(1),
+        });
+
+        let start = Instant::now();
+        for _ in 0..5 {
+            limiter.acquire().await;
+        }
+
+        assert!(start.elapsed() >= Duration::from_millis(1500));
+        assert!(start.elapsed() < Duration::from_millis(1600));
+    }
+
+    #[test]
+    fn test_retry_delay() {
+        let policy = RetryPolicy {
+            max_retries: 5,
+            base_delay: Duration::from_secs(1),
+            max_delay: Duration::from_secs(5),
+        };
+
+        for (attempt, max) in [(0, 1), (1, 2), (2, 4), (3, 5), (4, 5)] {
+            let delay = policy.delay(attempt);
+            let max = Duration::from_secs(max);
+            assert!(delay >= max / 2 && delay <= max, "{:?}", delay);
+        }
+    }
+}
@@ -0,0 +1,129 @@
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use reqwest::{
+    header::{HeaderMap, LOCATION, RETRY_AFTER},
+    StatusCode,
+};
+
+/// How a response of the API should be treated.
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum ResponseKind {
+    Success,
+    NotFound,
+    RateLimited { retry_after: Option<Duration> },
+    ServerError,
+    Redirect { location: Option<String> },
+    ClientError,
+}
+
+impl ResponseKind {
+    pub(crate) fn classify(status: StatusCode, headers: &HeaderMap) -> Self {
+        match status {
+            StatusCode::NOT_FOUND => ResponseKind::NotFound,
+            StatusCode::TOO_MANY_REQUESTS => ResponseKind::RateLimited {
+                retry_after: headers
+                    .get(RETRY_AFTER)
+                    .and_then(|v| v.to_str().ok())
+                    .and_then(parse_retry_after),
+            },
+            status if status.is_success() => ResponseKind::Success,
+            status if status.is_redirection() => ResponseKind::Redirect {
+                location: headers
+                    .get(LOCATION)
+                    .and_then(|v| v.to_str().ok())
+                    .map(str::to_string),
+            },
+            status if status.is_server_error() => ResponseKind::ServerError,
+            _ => ResponseKind::ClientError,
+        }
+    }
+
+    pub(crate) fn is_retryable(&self) -> bool {
+        matches!(
+            self,
+            ResponseKind::RateLimited { .. } | ResponseKind::ServerError
+        )
+    }
+}
+
+/// `Retry-After` is either a number of seconds or a HTTP date.
+fn parse_retry_after(value: &str) -> Option<Duration> {
+    if let Ok(secs) = value.trim().parse() {
+        return Some(Duration::from_secs(secs));
+    }
+
+    let date = DateTime::parse_from_rfc2822(value).ok()?;
+    Some(
+        (date.with_timezone(&Utc) - Utc::now())
+            .to_std()
+            .unwrap_or_default(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use reqwest::{
+        header::{HeaderMap, HeaderValue, LOCATION, RETRY_AFTER},
+        StatusCode,
+    };
+
+    use super::ResponseKind;
+
+    #[test]
+    fn test_classify() {
+        let empty = HeaderMap::new();
+
+        assert_eq!(
+            ResponseKind::classify(StatusCode::OK, &empty),
+            ResponseKind::Success
+        );
+        assert_eq!(
+            ResponseKind::classify(StatusCode::NOT_FOUND, &empty),
+            ResponseKind::NotFound
+        );
+        assert_eq!(
+            ResponseKind::classify(StatusCode::SERVICE_UNAVAILABLE, &empty),
+            ResponseKind::ServerError
+        );
+        assert_eq!(
+            ResponseKind::classify(StatusCode::BAD_REQUEST, &empty),
+            ResponseKind::ClientError
+        );
+
+        let mut headers = HeaderMap::new();
+        headers.insert(RETRY_AFTER, HeaderValue::from_static("7"));
+        headers.insert(LOCATION, HeaderValue::from_static("/shows/1"));
+
+        assert_eq!(
+            ResponseKind::classify(StatusCode::TOO_MANY_REQUESTS, &headers),
+            ResponseKind::RateLimited {
+                retry_after: Some(Duration::from_secs(7))
+            }
+        );
+        assert_eq!(
+            ResponseKind::classify(StatusCode::FOUND, &headers),
+            ResponseKind::Redirect {
+                location: Some("/shows/1".to_string())
+            }
+        );
+    }
+
+    #[test]
+    fn test_retry_after_in_past() {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            RETRY_AFTER,
+            HeaderValue::from_static("Wed, 21 Oct 2015 07:28:00 GMT"),
+        );
+
+        assert_eq!(
+            ResponseKind::classify(StatusCode::TOO_MANY_REQUESTS, &headers),
+            ResponseKind::RateLimited {
+                retry_after: Some(Duration::ZERO)
+            }
+        );
+    }
+}
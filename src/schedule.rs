@@ -1,62 +1,198 @@
-use crate::models::Episode;
-
-// TODO: params are optional
-/// The schedule is a complete list of episodes that air in a given country on a given date. Episodes are returned in the order in which they are aired, and full information about the episode and the corresponding show is included.
-pub async fn schedule(country_code: &str, date: &str) -> Result<Vec<Episode>, reqwest::Error> {
-    Ok(serde_json::from_str(
-        &reqwest::get(format!(
-            "https://api.tvmaze.com/schedule?country={}&date={}",
-            country_code, date
-        ))
-        .await?
-        .text()
-        .await?,
-    )
-    .unwrap())
-}
-
-// TODO: params are optional, country_code has special fn if empty
-/// The web schedule is a complete list of episodes that air on web/streaming channels on a given date. TVmaze distinguishes between local and global Web Channels: local Web Channels are only available in one specific country, while global Web Channels are available in multiple countries. To query both local and global Web Channels, leave out the country parameter. To query only local Web Channels, set country to an ISO country code. And to query only global Web Channels, set country to an empty string.
-pub async fn web_schedule(country_code: &str, date: &str) -> Result<Vec<Episode>, reqwest::Error> {
-    Ok(serde_json::from_str(
-        &reqwest::get(format!(
-            "https://api.tvmaze.com/schedule/web?country={}&date={}",
-            country_code, date
-        ))
-        .await?
-        .text()
-        .await?,
-    )
-    .unwrap())
-}
-
-/// The full schedule is a list of all future episodes known to TVmaze, regardless of their country. Be advised that this endpoint's response is at least several MB large. As opposed to the other endpoints, results are cached for 24 hours.
-pub async fn full_schedule() -> Result<Vec<Episode>, reqwest::Error> {
-    Ok(serde_json::from_str(
-        &reqwest::get("https://api.tvmaze.com/schedule/full")
-            .await?
-            .text()
-            .await?,
-    )
-    .unwrap())
+use std::fmt::Display;
+
+use chrono::NaiveDate;
+
+use crate::{client::with_query, models::ScheduledEpisode, Result, TvMazeClient};
+
+/// Which web channels the web schedule covers.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum WebScheduleCountry {
+    /// Web channels local to the country with this ISO 3166-1 code.
+    Local(String),
+    /// Only global web channels.
+    Global,
+    /// Both local and global web channels, which is also what the web schedule defaults to.
+    Both,
+}
+
+impl From<&str> for WebScheduleCountry {
+    fn from(code: &str) -> Self {
+        WebScheduleCountry::Local(code.to_string())
+    }
+}
+
+impl From<String> for WebScheduleCountry {
+    fn from(code: String) -> Self {
+        WebScheduleCountry::Local(code)
+    }
+}
+
+/// Filters for [`TvMazeClient::schedule`]. Without a country the schedule defaults to the US, and without a date to the current day.
+#[derive(Clone, Debug, Default)]
+pub struct ScheduleQuery {
+    country: Option<String>,
+    date: Option<NaiveDate>,
+}
+
+impl ScheduleQuery {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only channels of the country with this ISO 3166-1 code.
+    pub fn country(mut self, code: impl Into<String>) -> Self {
+        self.country = Some(code.into());
+        self
+    }
+
+    pub fn date(mut self, date: NaiveDate) -> Self {
+        self.date = Some(date);
+        self
+    }
+}
+
+impl Display for ScheduleQuery {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&params(self.country.as_deref(), self.date))
+    }
+}
+
+/// Filters for [`TvMazeClient::web_schedule`]. Without a country the web schedule covers both local and global web channels, and without a date the current day.
+#[derive(Clone, Debug, Default)]
+pub struct WebScheduleQuery {
+    country: Option<WebScheduleCountry>,
+    date: Option<NaiveDate>,
+}
+
+impl WebScheduleQuery {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn country(mut self, country: impl Into<WebScheduleCountry>) -> Self {
+        self.country = Some(country.into());
+        self
+    }
+
+    pub fn date(mut self, date: NaiveDate) -> Self {
+        self.date = Some(date);
+        self
+    }
+}
+
+impl Display for WebScheduleQuery {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let country = match &self.country {
+            Some(WebScheduleCountry::Local(code)) => Some(code.as_str()),
+            Some(WebScheduleCountry::Global) => Some(""),
+            Some(WebScheduleCountry::Both) | None => None,
+        };
+        f.write_str(&params(country, self.date))
+    }
+}
+
+fn params(country: Option<&str>, date: Option<NaiveDate>) -> String {
+    let mut params = Vec::new();
+    if let Some(country) = country {
+        params.push(format!("country={}", country));
+    }
+    if let Some(date) = date {
+        params.push(format!("date={}", date.format("%Y-%m-%d")));
+    }
+    params.join("&")
+}
+
+impl TvMazeClient {
+    /// The schedule is a complete list of episodes that air in a given country on a given date. Episodes are returned in the order in which they are aired, and full information about the episode and the corresponding show is included.
+    pub async fn schedule(&self, query: &ScheduleQuery) -> Result<Vec<ScheduledEpisode>> {
+        self.get(&with_query("/schedule", query)).await
+    }
+
+    /// The web schedule is a complete list of episodes that air on web/streaming channels on a given date. TVmaze distinguishes between local and global Web Channels: local Web Channels are only available in one specific country, while global Web Channels are available in multiple countries. Use [`WebScheduleCountry`] to pick local, global or both.
+    pub async fn web_schedule(&self, query: &WebScheduleQuery) -> Result<Vec<ScheduledEpisode>> {
+        self.get(&with_query("/schedule/web", query)).await
+    }
+
+    /// The full schedule is a list of all future episodes known to TVmaze, regardless of their country. Be advised that this endpoint's response is at least several MB large. As opposed to the other endpoints, results are cached for 24 hours.
+    pub async fn full_schedule(&self) -> Result<Vec<ScheduledEpisode>> {
+        self.get("/schedule/full").await
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{full_schedule, schedule, web_schedule};
+    use chrono::NaiveDate;
+
+    use super::{ScheduleQuery, WebScheduleCountry, WebScheduleQuery};
+    use crate::tests::fixture_client;
+
+    fn date() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2020, 5, 29).unwrap()
+    }
+
+    #[test]
+    fn test_schedule_query() {
+        assert_eq!(ScheduleQuery::new().to_string(), "");
+        assert_eq!(
+            ScheduleQuery::new().country("GB").date(date()).to_string(),
+            "country=GB&date=2020-05-29"
+        );
+    }
+
+    #[test]
+    fn test_web_schedule_query() {
+        assert_eq!(WebScheduleQuery::new().to_string(), "");
+        assert_eq!(
+            WebScheduleQuery::new()
+                .country("GB")
+                .date(date())
+                .to_string(),
+            "country=GB&date=2020-05-29"
+        );
+        assert_eq!(
+            WebScheduleQuery::new()
+                .country(WebScheduleCountry::Global)
+                .to_string(),
+            "country="
+        );
+        assert_eq!(
+            WebScheduleQuery::new()
+                .country(WebScheduleCountry::Both)
+                .date(date())
+                .to_string(),
+            "date=2020-05-29"
+        );
+    }
 
     #[tokio::test]
     async fn test_schedule() {
-        schedule("US", "2014-12-01").await.unwrap();
+        let client = fixture_client();
+        let query = ScheduleQuery::new()
+            .country("US")
+            .date(NaiveDate::from_ymd_opt(2014, 12, 1).unwrap());
+        let episodes = client.schedule(&query).await.unwrap();
+        assert_eq!(episodes[0].show.name, "Person of Interest");
+        assert_eq!(episodes[0].episode.number, Some(9));
     }
 
     #[tokio::test]
     async fn test_web_schedule() {
-        web_schedule("US", "2020-05-29").await.unwrap();
+        let client = fixture_client();
+        let query = WebScheduleQuery::new().country("US").date(date());
+        let episodes = client.web_schedule(&query).await.unwrap();
+        assert_eq!(episodes[0].show.name, "Stranger Things");
+
+        let query = WebScheduleQuery::new()
+            .country(WebScheduleCountry::Global)
+            .date(date());
+        assert_eq!(client.web_schedule(&query).await.unwrap().len(), 1);
     }
 
     #[tokio::test]
     async fn test_full_schedule() {
-        full_schedule().await.unwrap();
+        let client = fixture_client();
+        let episodes = client.full_schedule().await.unwrap();
+        assert_eq!(episodes[1].show.name, "Stranger Things");
+        assert_eq!(episodes[1].episode.number, Some(2));
     }
 }
@@ -1,10 +1,9 @@
 use std::fmt::Display;
 
-use reqwest::StatusCode;
-
 use crate::{
-    models::{SearchResult, SearchResultPerson, Show},
-    Embed,
+    embed::{self, ShowEmbed},
+    models::{SearchResult, SearchResultNetwork, SearchResultPerson, SearchResultWebChannel, Show},
+    Result, TvMazeClient,
 };
 
 pub enum ExternId {
@@ -23,60 +22,66 @@ impl Display for ExternId {
     }
 }
 
-/// Search through all the shows in our database by the show's name. A fuzzy algorithm is used (with a fuzziness value of 2), meaning that shows will be found even if your query contains small typos. Results are returned in order of relevancy (best matches on top) and contain each show's full information.
-pub async fn show_search(query: &str) -> Result<Vec<SearchResult>, reqwest::Error> {
-    Ok(serde_json::from_str(
-        &reqwest::get(format!("https://api.tvmaze.com/search/shows?q={}", query))
-            .await?
-            .text()
-            .await?,
-    )
-    .unwrap())
-}
+impl TvMazeClient {
+    /// Search through all the shows in our database by the show's name. A fuzzy algorithm is used (with a fuzziness value of 2), meaning that shows will be found even if your query contains small typos. Results are returned in order of relevancy (best matches on top) and contain each show's full information.
+    pub async fn show_search(&self, query: &str) -> Result<Vec<SearchResult>> {
+        self.get(&format!("/search/shows?q={}", query)).await
+    }
 
-/// In some scenarios you might want to immediately return information based on a user's query, without the intermediary step of presenting them all the possible matches. In that case, you can use the singlesearch endpoint which either returns exactly one result, or no result at all. This endpoint is also forgiving of typos, but less so than the regular search (with a fuzziness of 1 instead of 2), to reduce the chance of a false positive.
-pub async fn show_single_search(query: &str, embed: Embed) -> Result<Option<Show>, reqwest::Error> {
-    Ok(serde_json::from_str(
-        &reqwest::get(format!(
-            "https://api.tvmaze.com/singlesearch/shows?q={}&{}",
-            query, embed
-        ))
-        .await?
-        .text()
-        .await?,
-    )
-    .unwrap())
-}
+    /// In some scenarios you might want to immediately return information based on a user's query, without the intermediary step of presenting them all the possible matches. In that case, you can use the singlesearch endpoint which either returns exactly one result, or no result at all. This endpoint is also forgiving of typos, but less so than the regular search (with a fuzziness of 1 instead of 2), to reduce the chance of a false positive.
+    pub async fn show_single_search<E: ShowEmbed>(
+        &self,
+        query: &str,
+        embed: E,
+    ) -> Result<Option<Show<E>>> {
+        let mut params = format!("q={}", query);
+        let embed = embed::query(&embed);
+        if !embed.is_empty() {
+            params = format!("{}&{}", params, embed);
+        }
+        self.get_notfoundable(&format!("/singlesearch/shows?{}", params))
+            .await
+    }
 
-/// If you already know a show's tvrage, thetvdb or IMDB ID, you can use this endpoint to find this exact show on TVmaze. If the given ID can be matched, a HTTP 302 redirect to the show's URL will be returned. Otherwise, a HTTP 404 is sent.
-pub async fn show_lookup(extern_id: ExternId) -> Result<Option<Show>, reqwest::Error> {
-    crate::notfoundable_endpoint!(format!("https://api.tvmaze.com/lookup/shows?{}", extern_id))
-}
+    /// If you already know a show's tvrage, thetvdb or IMDB ID, you can use this endpoint to find this exact show on TVmaze. If the given ID can be matched, a HTTP 302 redirect to the show's URL will be returned. Otherwise, a HTTP 404 is sent.
+    pub async fn show_lookup(&self, extern_id: ExternId) -> Result<Option<Show>> {
+        self.get_notfoundable(&format!("/lookup/shows?{}", extern_id))
+            .await
+    }
+
+    /// Search through all the people in our database, using the same mechanism as described for show searches.
+    pub async fn people_search(&self, query: &str) -> Result<Vec<SearchResultPerson>> {
+        self.get(&format!("/search/people?q={}", query)).await
+    }
 
-/// Search through all the people in our database, using the same mechanism as described for show searches.
-pub async fn people_search(query: &str) -> Result<Vec<SearchResultPerson>, reqwest::Error> {
-    Ok(serde_json::from_str(
-        &reqwest::get(format!("https://api.tvmaze.com/search/people?q={}", query))
-            .await?
-            .text()
-            .await?,
-    )
-    .unwrap())
+    /// Search through all the networks, using the same mechanism as described for show searches.
+    pub async fn network_search(&self, query: &str) -> Result<Vec<SearchResultNetwork>> {
+        self.get(&format!("/search/networks?q={}", query)).await
+    }
+
+    /// Search through all the web channels, using the same mechanism as described for show searches.
+    pub async fn web_channel_search(&self, query: &str) -> Result<Vec<SearchResultWebChannel>> {
+        self.get(&format!("/search/webchannels?q={}", query)).await
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::sync::Arc;
+
+    use reqwest::StatusCode;
+
     use crate::{
-        search::{show_lookup, show_search, show_single_search, ExternId},
-        tests::{FULL_EMBED, TEST_ACTORS, TEST_SHOWS},
+        search::ExternId,
+        tests::{fixture_client, memory_client, FULL_EMBED, TEST_ACTORS, TEST_SHOWS},
+        transport::{MemoryTransport, Response},
     };
 
-    use super::people_search;
-
     #[tokio::test]
     async fn test_show_search() {
+        let client = fixture_client();
         for (name, id, tvdb) in TEST_SHOWS {
-            let res = &show_search(name).await.unwrap()[0].show;
+            let res = &client.show_search(name).await.unwrap()[0].show;
 
             assert_eq!(res.name, name);
             assert_eq!(res.id, id);
@@ -84,10 +89,31 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_show_single_search_no_match() {
+        let transport = Arc::new(MemoryTransport::new().with_response(
+            "/singlesearch/shows?q=qwxzy",
+            Response::new(StatusCode::NOT_FOUND, r#"{"name": "Not Found"}"#),
+        ));
+        let client = memory_client(transport.clone());
+
+        assert!(client
+            .show_single_search("qwxzy", ())
+            .await
+            .unwrap()
+            .is_none());
+        assert_eq!(transport.requests().len(), 1);
+    }
+
     #[tokio::test]
     async fn test_show_single_search() {
+        let client = fixture_client();
         for (name, id, tvdb) in TEST_SHOWS {
-            let res = show_single_search(name, FULL_EMBED).await.unwrap().unwrap();
+            let res = client
+                .show_single_search(name, FULL_EMBED)
+                .await
+                .unwrap()
+                .unwrap();
 
             assert_eq!(res.name, name);
             assert_eq!(res.id, id);
@@ -97,8 +123,13 @@ mod tests {
 
     #[tokio::test]
     async fn test_show_lookup() {
+        let client = fixture_client();
         for (name, id, tvdb) in TEST_SHOWS {
-            let res = show_lookup(ExternId::TVDB(tvdb)).await.unwrap().unwrap();
+            let res = client
+                .show_lookup(ExternId::TVDB(tvdb))
+                .await
+                .unwrap()
+                .unwrap();
 
             assert_eq!(res.name, name);
             assert_eq!(res.id, id);
@@ -108,8 +139,24 @@ mod tests {
 
     #[tokio::test]
     async fn test_people_search() {
+        let client = fixture_client();
         for x in TEST_ACTORS {
-            people_search(x).await.unwrap();
+            client.people_search(x).await.unwrap();
         }
     }
+
+    #[tokio::test]
+    async fn test_network_search() {
+        let client = fixture_client();
+        let res = &client.network_search("cbs").await.unwrap()[0].network;
+        assert_eq!(res.name, "CBS");
+    }
+
+    #[tokio::test]
+    async fn test_web_channel_search() {
+        let client = fixture_client();
+        let res = &client.web_channel_search("netflix").await.unwrap()[0].web_channel;
+        assert_eq!(res.name, "Netflix");
+        assert!(res.country.is_none());
+    }
 }
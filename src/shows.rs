@@ -1,116 +1,186 @@
-use reqwest::StatusCode;
+use futures_util::Stream;
 
 use crate::{
-    models::{Aka, AlternateList, CastMember, CrewMember, Episode, Season, Show, ShowImage},
-    Embed,
+    client::with_query,
+    embed::{self, AlternateEpisodeEmbed, AlternateListEmbed, SeasonEmbed, ShowEmbed},
+    index::index_stream,
+    models::{
+        Aka, AlternateEpisode, AlternateList, CastMember, CrewMember, Episode, Season, Show,
+        ShowId, ShowImage,
+    },
+    Result, TvMazeClient,
 };
 
-/// Retrieve all primary information for a given show. This endpoint allows embedding of additional information. See the section embedding for more information.
-pub async fn show(id: usize, embed: Embed) -> Result<Option<Show>, reqwest::Error> {
-    crate::notfoundable_endpoint!(format!("https://api.tvmaze.com/shows/{}?{}", id, embed))
-}
+/// Number of shows covered by a page of the show index.
+pub(crate) const SHOW_INDEX_PAGE_SIZE: usize = 250;
 
-/// A complete list of episodes for the given show. Episodes are returned in their airing order, and include full episode information. By default, specials are not included in the list.
-pub async fn show_episode_list(
-    show: usize,
-    specials: bool,
-) -> Result<Option<Vec<Episode>>, reqwest::Error> {
-    crate::notfoundable_endpoint!(format!(
-        "https://api.tvmaze.com/shows/{}/episodes{}",
-        show,
-        if specials { "?specials=1" } else { "" }
-    ))
-}
+impl TvMazeClient {
+    /// Retrieve all primary information for a given show. This endpoint allows embedding of additional information. See the section embedding for more information.
+    pub async fn show<E: ShowEmbed>(&self, id: usize, embed: E) -> Result<Option<Show<E>>> {
+        self.get_notfoundable(&with_query(&format!("/shows/{}", id), embed::query(&embed)))
+            .await
+    }
 
-// TODO: alternate lists embed
-// TODO: all the other alternate list endpoints
-/// Alternate episode lists for this show, for example DVD ordering. For a description of the different types of alternate lists that you can find, please refer to the alternate episode policy.
-pub async fn show_alternate_lists(
-    show: usize,
-) -> Result<Option<Vec<AlternateList>>, reqwest::Error> {
-    crate::notfoundable_endpoint!(format!(
-        "https://api.tvmaze.com/shows/{}/alternatelists",
-        show,
-    ))
-}
+    /// A complete list of episodes for the given show. Episodes are returned in their airing order, and include full episode information. By default, specials are not included in the list.
+    pub async fn show_episode_list(
+        &self,
+        show: usize,
+        specials: bool,
+    ) -> Result<Option<Vec<Episode>>> {
+        self.get_notfoundable(&format!(
+            "/shows/{}/episodes{}",
+            show,
+            if specials { "?specials=1" } else { "" }
+        ))
+        .await
+    }
 
-/// Retrieve one specific episode from this show given its season number and episode number. This either returns the full information for one episode, or a HTTP 404.
-pub async fn episode_by_number(
-    show: usize,
-    season: usize,
-    episode: usize,
-) -> Result<Option<Episode>, reqwest::Error> {
-    crate::notfoundable_endpoint!(format!(
-        "https://api.tvmaze.com/shows/{}/episodebynumber?season={}&number={}",
-        show, season, episode,
-    ))
-}
+    /// Alternate episode lists for this show, for example DVD ordering. For a description of the different types of alternate lists that you can find, please refer to the alternate episode policy.
+    pub async fn show_alternate_lists(&self, show: usize) -> Result<Option<Vec<AlternateList>>> {
+        self.get_notfoundable(&format!("/shows/{}/alternatelists", show))
+            .await
+    }
 
-/// Retrieve all episodes from this show that have aired on a specific date. This either returns an array of full episode info, or a HTTP 404. Useful for daily (talk) shows that don't adhere to a common season numbering.
-pub async fn episodes_by_date(
-    show: usize,
-    date: &str,
-) -> Result<Option<Vec<Episode>>, reqwest::Error> {
-    crate::notfoundable_endpoint!(format!(
-        "https://api.tvmaze.com/shows/{}/episodesbydate?date={}",
-        show, date
-    ))
-}
+    /// Retrieve a specific alternate list. This endpoint allows embedding of its alternate episodes.
+    pub async fn alternate_list<E: AlternateListEmbed>(
+        &self,
+        id: usize,
+        embed: E,
+    ) -> Result<Option<AlternateList<E>>> {
+        self.get_notfoundable(&with_query(
+            &format!("/alternatelists/{}", id),
+            embed::query(&embed),
+        ))
+        .await
+    }
 
-/// A complete list of seasons for the given show. Seasons are returned in ascending order and contain the full information that's known about them.
-pub async fn show_seasons(show: usize) -> Result<Option<Vec<Season>>, reqwest::Error> {
-    crate::notfoundable_endpoint!(format!("https://api.tvmaze.com/shows/{}/seasons", show,))
-}
+    /// The episodes of an alternate list, in the alternate order. By default only the alternate position is returned; embed `Episodes` to include the episodes each position stands for.
+    pub async fn alternate_episodes<E: AlternateEpisodeEmbed>(
+        &self,
+        list: usize,
+        embed: E,
+    ) -> Result<Option<Vec<AlternateEpisode<E>>>> {
+        self.get_notfoundable(&with_query(
+            &format!("/alternatelists/{}/alternateepisodes", list),
+            embed::query(&embed),
+        ))
+        .await
+    }
 
-/// A list of episodes in this season. Specials are always included in this list.
-pub async fn season_episodes(season: usize) -> Result<Option<Vec<Episode>>, reqwest::Error> {
-    crate::notfoundable_endpoint!(format!(
-        "https://api.tvmaze.com/seasons/{}/episodes",
-        season,
-    ))
-}
+    /// Retrieve one specific episode from this show given its season number and episode number. This either returns the full information for one episode, or a HTTP 404.
+    pub async fn episode_by_number(
+        &self,
+        show: usize,
+        season: usize,
+        episode: usize,
+    ) -> Result<Option<Episode>> {
+        self.get_notfoundable(&format!(
+            "/shows/{}/episodebynumber?season={}&number={}",
+            show, season, episode,
+        ))
+        .await
+    }
 
-/// A list of main cast for a show. Each cast item is a combination of a person and a character. Items are ordered by importance, which is determined by the total number of appearances of the given character in this show.
-pub async fn show_cast(show: usize) -> Result<Option<Vec<CastMember>>, reqwest::Error> {
-    crate::notfoundable_endpoint!(format!("https://api.tvmaze.com/shows/{}/cast", show,))
-}
+    /// Retrieve all episodes from this show that have aired on a specific date. This either returns an array of full episode info, or a HTTP 404. Useful for daily (talk) shows that don't adhere to a common season numbering.
+    pub async fn episodes_by_date(&self, show: usize, date: &str) -> Result<Option<Vec<Episode>>> {
+        self.get_notfoundable(&format!("/shows/{}/episodesbydate?date={}", show, date))
+            .await
+    }
 
-/// A list of main crew for a show. Each crew item is a combination of a person and their crew type.
-pub async fn show_crew(show: usize) -> Result<Option<Vec<CrewMember>>, reqwest::Error> {
-    crate::notfoundable_endpoint!(format!("https://api.tvmaze.com/shows/{}/crew", show,))
-}
+    /// A complete list of seasons for the given show. Seasons are returned in ascending order and contain the full information that's known about them.
+    pub async fn show_seasons<E: SeasonEmbed>(
+        &self,
+        show: usize,
+        embed: E,
+    ) -> Result<Option<Vec<Season<E>>>> {
+        self.get_notfoundable(&with_query(
+            &format!("/shows/{}/seasons", show),
+            embed::query(&embed),
+        ))
+        .await
+    }
 
-/// A list of AKA's (aliases) for a show. An AKA with its country set to null indicates an AKA in the show's original country. Otherwise, it's the AKA for that show in the given foreign country.
-pub async fn show_akas(show: usize) -> Result<Option<Vec<Aka>>, reqwest::Error> {
-    crate::notfoundable_endpoint!(format!("https://api.tvmaze.com/shows/{}/akas", show,))
-}
+    /// Retrieve all primary information for a given season. This endpoint allows embedding of its episodes.
+    pub async fn season<E: SeasonEmbed>(&self, id: usize, embed: E) -> Result<Option<Season<E>>> {
+        self.get_notfoundable(&with_query(
+            &format!("/seasons/{}", id),
+            embed::query(&embed),
+        ))
+        .await
+    }
 
-/// A list of all images available for this show. The image type can be "poster", "banner", "background", "typography", or NULL in case of legacy unclassified images. For a definition of these types, please refer to the main image and general image policies.
-pub async fn show_images(show: usize) -> Result<Option<Vec<ShowImage>>, reqwest::Error> {
-    crate::notfoundable_endpoint!(format!("https://api.tvmaze.com/shows/{}/images", show,))
-}
+    /// A list of episodes in this season. Specials are always included in this list.
+    pub async fn season_episodes(&self, season: usize) -> Result<Option<Vec<Episode>>> {
+        self.get_notfoundable(&format!("/seasons/{}/episodes", season))
+            .await
+    }
+
+    /// A list of main cast for a show. Each cast item is a combination of a person and a character. Items are ordered by importance, which is determined by the total number of appearances of the given character in this show.
+    pub async fn show_cast(&self, show: usize) -> Result<Option<Vec<CastMember>>> {
+        self.get_notfoundable(&format!("/shows/{}/cast", show))
+            .await
+    }
+
+    /// A list of main crew for a show. Each crew item is a combination of a person and their crew type.
+    pub async fn show_crew(&self, show: usize) -> Result<Option<Vec<CrewMember>>> {
+        self.get_notfoundable(&format!("/shows/{}/crew", show))
+            .await
+    }
 
-/// A list of all shows in our database, with all primary information included. You can use this endpoint for example if you want to build a local cache of all shows contained in the TVmaze database. This endpoint is paginated, with a maximum of 250 results per page. The pagination is based on show ID, e.g. page 0 will contain shows with IDs between 0 and 250. This means a single page might contain less than 250 results, in case of deletions, but it also guarantees that deletions won't cause shuffling in the page numbering for other shows.
-pub async fn show_index(page: usize) -> Result<Option<Vec<Show>>, reqwest::Error> {
-    crate::notfoundable_endpoint!(format!("https://api.tvmaze.com/shows?page={}", page,))
+    /// A list of AKA's (aliases) for a show. An AKA with its country set to null indicates an AKA in the show's original country. Otherwise, it's the AKA for that show in the given foreign country.
+    pub async fn show_akas(&self, show: usize) -> Result<Option<Vec<Aka>>> {
+        self.get_notfoundable(&format!("/shows/{}/akas", show))
+            .await
+    }
+
+    /// A list of all images available for this show. The image type can be "poster", "banner", "background", "typography", or NULL in case of legacy unclassified images. For a definition of these types, please refer to the main image and general image policies.
+    pub async fn show_images(&self, show: usize) -> Result<Option<Vec<ShowImage>>> {
+        self.get_notfoundable(&format!("/shows/{}/images", show))
+            .await
+    }
+
+    /// A list of all shows in our database, with all primary information included. You can use this endpoint for example if you want to build a local cache of all shows contained in the TVmaze database. This endpoint is paginated, with a maximum of 250 results per page. The pagination is based on show ID, e.g. page 0 will contain shows with IDs between 0 and 250. This means a single page might contain less than 250 results, in case of deletions, but it also guarantees that deletions won't cause shuffling in the page numbering for other shows.
+    pub async fn show_index(&self, page: usize) -> Result<Option<Vec<Show>>> {
+        self.get_notfoundable(&format!("/shows?page={}", page))
+            .await
+    }
+
+    /// Every show of the [show index](TvMazeClient::show_index), walking its pages until the end, optionally starting from the show with ID `start`. The next page is requested while the current one is consumed. The stream ends after the first error.
+    pub fn show_index_stream(
+        &self,
+        start: Option<ShowId>,
+    ) -> impl Stream<Item = Result<Show>> + '_ {
+        let start = start.map_or(0, |id| id.0);
+        index_stream(
+            start / SHOW_INDEX_PAGE_SIZE,
+            move |page| self.show_index(page),
+            move |show: &Show| show.id >= start,
+        )
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{
-        shows::{show, show_index},
-        tests::{FULL_EMBED, TEST_SHOWS},
-    };
+    use std::sync::Arc;
+
+    use futures_util::StreamExt;
+    use reqwest::StatusCode;
 
-    use super::{
-        episode_by_number, episodes_by_date, season_episodes, show_akas, show_alternate_lists,
-        show_cast, show_crew, show_episode_list, show_images, show_seasons,
+    use crate::{
+        embed::{
+            Akas, AlternateEpisodes, Cast, Crew, Episodes, Images, NextEpisode, PreviousEpisode,
+            Seasons,
+        },
+        models::{SeasonWithEpisodes, ShowId},
+        tests::{fixture_client, index_page, memory_client, FULL_EMBED, TEST_SHOWS},
+        transport::{MemoryTransport, Response},
     };
 
     #[tokio::test]
     async fn test_show() {
+        let client = fixture_client();
         for (name, id, tvdb) in TEST_SHOWS {
-            let res = show(id, FULL_EMBED).await.unwrap().unwrap();
+            let res = client.show(id, FULL_EMBED).await.unwrap().unwrap();
 
             assert_eq!(res.name, name);
             assert_eq!(res.id, id);
@@ -118,80 +188,242 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_show_embed_all() {
+        let client = fixture_client();
+        let embed = (
+            Episodes,
+            Cast,
+            NextEpisode,
+            PreviousEpisode,
+            Crew,
+            Seasons,
+            Images,
+            Akas,
+        );
+        let res = client.show(1371, embed).await.unwrap().unwrap();
+        let (episodes, cast, next, previous, crew, seasons, images, akas) =
+            res._embedded.into_inner();
+
+        // Westworld's four seasons have 10, 10, 8 and 8 episodes.
+        assert_eq!(episodes.len(), 36);
+        assert_eq!(seasons.len(), 4);
+        assert_eq!(previous.unwrap().name, "Que Será, Será");
+        assert!(next.is_none());
+        assert!(!cast.is_empty());
+        assert!(!crew.is_empty());
+        assert!(!images.is_empty());
+        assert!(!akas.is_empty());
+    }
+
     #[tokio::test]
     async fn test_show_episode_list() {
+        let client = fixture_client();
         assert_eq!(
-            show_episode_list(204, true).await.unwrap().unwrap().len(),
+            client
+                .show_episode_list(204, true)
+                .await
+                .unwrap()
+                .unwrap()
+                .len(),
             218
         )
     }
 
     #[tokio::test]
     async fn test_show_alternate_lists() {
-        show_alternate_lists(180).await.unwrap();
+        let client = fixture_client();
+        client.show_alternate_lists(180).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_alternate_list() {
+        let client = fixture_client();
+        let list = client.alternate_list(1, ()).await.unwrap().unwrap();
+        assert!(list.dvd_release);
+
+        let list = client
+            .alternate_list(1, AlternateEpisodes)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(list._embedded.len(), 14);
+    }
+
+    #[tokio::test]
+    async fn test_alternate_episodes() {
+        let client = fixture_client();
+        let lists = client.show_alternate_lists(180).await.unwrap().unwrap();
+        let episodes = client
+            .alternate_episodes(lists[0].id, Episodes)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(episodes.len(), 14);
+        assert_eq!(episodes[0].number, Some(1));
+        assert_eq!(episodes[0]._embedded[0].name, "Serenity");
+        assert_eq!(episodes[0]._embedded[0].number, Some(11));
+
+        let episodes = client.alternate_episodes(1, ()).await.unwrap().unwrap();
+        assert_eq!(episodes.len(), 14);
     }
 
     #[tokio::test]
     async fn test_episode_by_number() {
+        let client = fixture_client();
         assert_eq!(
             "Apotheosis".to_string(),
-            episode_by_number(433, 1, 18).await.unwrap().unwrap().name
+            client
+                .episode_by_number(433, 1, 18)
+                .await
+                .unwrap()
+                .unwrap()
+                .name
         )
     }
 
     #[tokio::test]
     async fn test_episodes_by_date() {
+        let client = fixture_client();
         assert_eq!(
             "A Christmas Carol".to_string(),
-            episodes_by_date(210, "2010-12-25").await.unwrap().unwrap()[0].name
+            client
+                .episodes_by_date(210, "2010-12-25")
+                .await
+                .unwrap()
+                .unwrap()[0]
+                .name
         )
     }
 
     #[tokio::test]
     async fn test_show_seasons() {
+        let client = fixture_client();
         for (_, id, _) in TEST_SHOWS {
-            show_seasons(id).await.unwrap();
+            client.show_seasons(id, ()).await.unwrap();
         }
     }
 
+    #[tokio::test]
+    async fn test_show_seasons_embed() {
+        let client = fixture_client();
+        let seasons: Vec<SeasonWithEpisodes> =
+            client.show_seasons(1371, Episodes).await.unwrap().unwrap();
+
+        // The fixture follows Westworld's real season lengths.
+        assert_eq!(
+            seasons
+                .iter()
+                .map(|season| season._embedded.len())
+                .collect::<Vec<_>>(),
+            [10, 10, 8, 8]
+        );
+        for season in &seasons {
+            assert_eq!(Some(season._embedded.len()), season.episode_order);
+            assert!(season
+                ._embedded
+                .iter()
+                .all(|episode| episode.season == season.number));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_season() {
+        let client = fixture_client();
+        let season = client.season(5281, ()).await.unwrap().unwrap();
+        assert_eq!(season.number, 1);
+
+        let season = client.season(5281, Episodes).await.unwrap().unwrap();
+        assert_eq!(season.episode_order, Some(10));
+        assert_eq!(season._embedded.len(), 10);
+    }
+
     #[tokio::test]
     async fn test_season_episodes() {
+        let client = fixture_client();
         for id in 1..10 {
-            season_episodes(id).await.unwrap();
+            client.season_episodes(id).await.unwrap();
         }
     }
 
     #[tokio::test]
     async fn test_show_cast() {
+        let client = fixture_client();
         for (_, id, _) in TEST_SHOWS {
-            show_cast(id).await.unwrap();
+            client.show_cast(id).await.unwrap();
         }
     }
 
     #[tokio::test]
     async fn test_show_crew() {
+        let client = fixture_client();
         for (_, id, _) in TEST_SHOWS {
-            show_crew(id).await.unwrap();
+            client.show_crew(id).await.unwrap();
         }
     }
 
     #[tokio::test]
     async fn test_show_akas() {
+        let client = fixture_client();
         for (_, id, _) in TEST_SHOWS {
-            show_akas(id).await.unwrap();
+            client.show_akas(id).await.unwrap();
         }
     }
 
     #[tokio::test]
     async fn test_show_images() {
+        let client = fixture_client();
         for (_, id, _) in TEST_SHOWS {
-            show_images(id).await.unwrap();
+            client.show_images(id).await.unwrap();
         }
     }
 
     #[tokio::test]
     async fn test_show_index() {
-        let res = &show_index(0).await.unwrap().unwrap()[0];
+        let client = fixture_client();
+        let res = &client.show_index(0).await.unwrap().unwrap()[0];
         assert!(res.id <= 250);
     }
+
+    #[tokio::test]
+    async fn test_show_index_stream() {
+        let transport = Arc::new(
+            MemoryTransport::new()
+                .with_json("/shows?page=0", index_page("/shows/1", [1, 2, 249]))
+                .with_json("/shows?page=1", index_page("/shows/1", [250, 499]))
+                .with_json("/shows?page=2", index_page("/shows/1", [500])),
+        );
+        let client = memory_client(transport.clone());
+        let ids = |start| {
+            client
+                .show_index_stream(start)
+                .map(|show| show.unwrap().id)
+                .collect::<Vec<_>>()
+        };
+
+        // Pages are requested until the first 404.
+        assert_eq!(ids(None).await, [1, 2, 249, 250, 499, 500]);
+        assert!(transport
+            .requests()
+            .last()
+            .unwrap()
+            .url
+            .ends_with("/shows?page=3"));
+
+        // Starting from an ID skips the pages before it.
+        let sent = transport.requests().len();
+        assert_eq!(ids(Some(ShowId(260))).await, [499, 500]);
+        assert!(transport.requests()[sent].url.ends_with("/shows?page=1"));
+        assert!(ids(Some(ShowId(1000))).await.is_empty());
+
+        // The stream ends with the first failed page, although later pages were prefetched.
+        let transport = MemoryTransport::new()
+            .with_json("/shows?page=0", index_page("/shows/1", [1]))
+            .with_response("/shows?page=1", Response::new(StatusCode::BAD_REQUEST, ""))
+            .with_json("/shows?page=2", index_page("/shows/1", [500]));
+        let client = memory_client(Arc::new(transport));
+        let shows: Vec<_> = client.show_index_stream(None).collect().await;
+        assert!(matches!(shows[..], [Ok(_), Err(_)]));
+    }
 }
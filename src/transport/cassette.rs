@@ -0,0 +1,215 @@
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use reqwest::{
+    header::{HeaderMap, HeaderName, HeaderValue},
+    StatusCode,
+};
+use serde::{Deserialize, Serialize};
+
+use super::{path_and_query, BoxFuture, Request, Response, Transport};
+use crate::{Error, Result};
+
+/// Records responses of another transport into fixture files, or replays them from those files without network. Every request is stored in its own file, named after the path and query of its URL, so fixtures can be shared between tests and reviewed individually.
+#[derive(Debug)]
+pub struct CassetteTransport {
+    dir: PathBuf,
+    recording: Option<Arc<dyn Transport>>,
+}
+
+impl CassetteTransport {
+    /// Forward requests to `inner` and write every response to `dir`, overwriting existing fixtures.
+    pub fn record(dir: impl Into<PathBuf>, inner: Arc<dyn Transport>) -> Self {
+        Self {
+            dir: dir.into(),
+            recording: Some(inner),
+        }
+    }
+
+    /// Answer requests from the fixtures in `dir`. Requests without a fixture fail with [`Error::Io`].
+    pub fn replay(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            recording: None,
+        }
+    }
+
+    /// Path of the fixture for `url`.
+    pub fn fixture_path(&self, url: &str) -> PathBuf {
+        self.dir.join(fixture_name(url))
+    }
+}
+
+impl Transport for CassetteTransport {
+    fn send(&self, request: Request) -> BoxFuture<'_, Result<Response>> {
+        Box::pin(async move {
+            let path = self.fixture_path(&request.url);
+            let url = path_and_query(&request.url);
+
+            match &self.recording {
+                Some(inner) => {
+                    let res = inner.send(request).await?;
+                    write_fixture(&path, &Fixture::from_response(url, &res))?;
+                    Ok(res)
+                }
+                None => read_fixture(&path)?.into_response(),
+            }
+        })
+    }
+}
+
+/// On-disk format of a single recorded response. JSON bodies are embedded as-is to keep fixtures readable, anything else is stored as a string.
+#[derive(Serialize, Deserialize, Debug)]
+struct Fixture {
+    url: String,
+    status: u16,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    headers: BTreeMap<String, String>,
+    body: serde_json::Value,
+}
+
+impl Fixture {
+    fn from_response(url: String, res: &Response) -> Self {
+        Self {
+            url,
+            status: res.status.as_u16(),
+            headers: res
+                .headers
+                .iter()
+                .filter_map(|(k, v)| Some((k.to_string(), v.to_str().ok()?.to_string())))
+                .collect(),
+            body: serde_json::from_str(&res.body)
+                .unwrap_or_else(|_| serde_json::Value::String(res.body.clone())),
+        }
+    }
+
+    fn into_response(self) -> Result<Response> {
+        let mut headers = HeaderMap::new();
+        for (k, v) in &self.headers {
+            headers.insert(
+                HeaderName::from_bytes(k.as_bytes())
+                    .map_err(|_| Error::InvalidConfig("invalid header name in fixture"))?,
+                HeaderValue::from_str(v)
+                    .map_err(|_| Error::InvalidConfig("invalid header value in fixture"))?,
+            );
+        }
+
+        Ok(Response {
+            status: StatusCode::from_u16(self.status)
+                .map_err(|_| Error::InvalidConfig("invalid status in fixture"))?,
+            headers,
+            body: match self.body {
+                serde_json::Value::String(body) => body,
+                body => body.to_string(),
+            },
+        })
+    }
+}
+
+fn read_fixture(path: &Path) -> Result<Fixture> {
+    let content = std::fs::read_to_string(path)?;
+    crate::client::parse(&path.to_string_lossy(), &content)
+}
+
+fn write_fixture(path: &Path, fixture: &Fixture) -> Result<()> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    let content = serde_json::to_string_pretty(fixture).expect("fixtures are serializable");
+    Ok(std::fs::write(path, content + "\n")?)
+}
+
+/// File name for `url`, keeping only characters that are safe on every file system.
+pub(crate) fn fixture_name(url: &str) -> String {
+    let name: String = path_and_query(url)
+        .chars()
+        .map(|c| match c {
+            'a'..='z' | 'A'..='Z' | '0'..='9' | '-' | '.' => c,
+            _ => '_',
+        })
+        .collect();
+
+    format!("{}.json", name.trim_start_matches('_'))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use reqwest::{
+        header::{HeaderValue, ETAG},
+        StatusCode,
+    };
+
+    use super::{fixture_name, CassetteTransport};
+    use crate::{
+        transport::{MemoryTransport, Request, Response, Transport},
+        Error,
+    };
+
+    #[test]
+    fn test_fixture_name() {
+        assert_eq!(
+            fixture_name("https://api.tvmaze.com/search/shows?q=Man Vs Bee"),
+            "search_shows_q_Man_20Vs_20Bee.json"
+        );
+        assert_eq!(
+            fixture_name("/shows/1?embed[]=cast"),
+            "shows_1_embed___cast.json"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_record_replay() {
+        let dir = std::env::temp_dir().join(format!("tvmaze-cassette-{}", std::process::id()));
+
+        let mut res = Response::json(r#"{"1": 2}"#);
+        res.headers
+            .insert(ETAG, HeaderValue::from_static("\"abc\""));
+        let inner = Arc::new(
+            MemoryTransport::new()
+                .with_response("/updates/shows", res)
+                .with_response(
+                    "/shows/1",
+                    Response::new(StatusCode::SERVICE_UNAVAILABLE, "<html>maintenance</html>"),
+                ),
+        );
+
+        let recorder = CassetteTransport::record(&dir, inner);
+        recorder
+            .send(Request::get("https://api.tvmaze.com/updates/shows"))
+            .await
+            .unwrap();
+        recorder
+            .send(Request::get("https://api.tvmaze.com/shows/1"))
+            .await
+            .unwrap();
+
+        let player = CassetteTransport::replay(&dir);
+
+        let res = player
+            .send(Request::get("http://localhost/updates/shows"))
+            .await
+            .unwrap();
+        assert_eq!(res.status, StatusCode::OK);
+        assert_eq!(res.headers[ETAG], "\"abc\"");
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&res.body).unwrap(),
+            serde_json::json!({"1": 2})
+        );
+
+        let res = player.send(Request::get("/shows/1")).await.unwrap();
+        assert_eq!(res.status, StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(res.body, "<html>maintenance</html>");
+
+        assert!(matches!(
+            player.send(Request::get("/shows/2")).await,
+            Err(Error::Io(_))
+        ));
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
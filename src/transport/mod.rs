@@ -0,0 +1,194 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    fmt::Debug,
+    future::Future,
+    pin::Pin,
+    sync::Mutex,
+};
+
+use reqwest::{header::HeaderMap, Method, StatusCode, Url};
+
+use crate::Result;
+
+mod cassette;
+pub use cassette::*;
+
+pub type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+/// A request as handed to a [`Transport`].
+#[derive(Clone, Debug)]
+pub struct Request {
+    pub method: Method,
+    pub url: String,
+    pub headers: HeaderMap,
+    pub body: Option<String>,
+}
+
+impl Request {
+    pub fn get(url: impl Into<String>) -> Self {
+        Self {
+            method: Method::GET,
+            url: url.into(),
+            headers: HeaderMap::new(),
+            body: None,
+        }
+    }
+}
+
+/// A fully read response returned by a [`Transport`].
+#[derive(Clone, Debug)]
+pub struct Response {
+    pub status: StatusCode,
+    pub headers: HeaderMap,
+    pub body: String,
+}
+
+impl Response {
+    pub fn new(status: StatusCode, body: impl Into<String>) -> Self {
+        Self {
+            status,
+            headers: HeaderMap::new(),
+            body: body.into(),
+        }
+    }
+
+    pub fn json(body: impl Into<String>) -> Self {
+        Self::new(StatusCode::OK, body)
+    }
+}
+
+/// Performs the HTTP requests of a [`crate::TvMazeClient`].
+pub trait Transport: Debug + Send + Sync {
+    fn send(&self, request: Request) -> BoxFuture<'_, Result<Response>>;
+}
+
+/// The default transport, backed by a [`reqwest::Client`].
+#[derive(Clone, Debug, Default)]
+pub struct ReqwestTransport {
+    http: reqwest::Client,
+}
+
+impl ReqwestTransport {
+    pub fn new(http: reqwest::Client) -> Self {
+        Self { http }
+    }
+}
+
+impl Transport for ReqwestTransport {
+    fn send(&self, request: Request) -> BoxFuture<'_, Result<Response>> {
+        Box::pin(async move {
+            let mut req = self
+                .http
+                .request(request.method, request.url)
+                .headers(request.headers);
+            if let Some(body) = request.body {
+                req = req.body(body);
+            }
+
+            let res = req.send().await?;
+            Ok(Response {
+                status: res.status(),
+                headers: res.headers().clone(),
+                body: res.text().await?,
+            })
+        })
+    }
+}
+
+/// Serves canned responses from memory, for testing without network. Responses are matched on the path and query of the requested URL, so they don't depend on the client's base URL. Unknown URLs are answered with a 404.
+#[derive(Debug, Default)]
+pub struct MemoryTransport {
+    responses: Mutex<HashMap<String, VecDeque<Response>>>,
+    requests: Mutex<Vec<Request>>,
+}
+
+impl MemoryTransport {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Serve `body` with a 200 status for `url`.
+    pub fn with_json(self, url: &str, body: impl Into<String>) -> Self {
+        self.with_response(url, Response::json(body))
+    }
+
+    /// Queue `response` for `url`. Queued responses are served in order, the last one is repeated indefinitely.
+    pub fn with_response(self, url: &str, response: Response) -> Self {
+        self.responses
+            .lock()
+            .unwrap()
+            .entry(path_and_query(url))
+            .or_default()
+            .push_back(response);
+        self
+    }
+
+    /// All requests sent so far, in order.
+    pub fn requests(&self) -> Vec<Request> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+impl Transport for MemoryTransport {
+    fn send(&self, request: Request) -> BoxFuture<'_, Result<Response>> {
+        let key = path_and_query(&request.url);
+        self.requests.lock().unwrap().push(request);
+
+        let mut responses = self.responses.lock().unwrap();
+        let res = match responses.get_mut(&key) {
+            Some(queue) if queue.len() > 1 => queue.pop_front().unwrap(),
+            Some(queue) => queue[0].clone(),
+            None => Response::new(StatusCode::NOT_FOUND, ""),
+        };
+
+        Box::pin(async move { Ok(res) })
+    }
+}
+
+/// Strip scheme and host from `url`, leaving relative URLs untouched.
+pub(crate) fn path_and_query(url: &str) -> String {
+    match Url::parse(url) {
+        Ok(url) => match url.query() {
+            Some(query) => format!("{}?{}", url.path(), query),
+            None => url.path().to_string(),
+        },
+        Err(_) => url.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use reqwest::StatusCode;
+
+    use super::{path_and_query, MemoryTransport, Request, Response, Transport};
+
+    #[test]
+    fn test_path_and_query() {
+        assert_eq!(
+            path_and_query("https://api.tvmaze.com/shows/1?embed=cast"),
+            "/shows/1?embed=cast"
+        );
+        assert_eq!(path_and_query("/shows/1"), "/shows/1");
+    }
+
+    #[tokio::test]
+    async fn test_memory_transport() {
+        let transport = MemoryTransport::new()
+            .with_response("/shows/1", Response::new(StatusCode::BAD_GATEWAY, ""))
+            .with_json("/shows/1", "{}");
+
+        let send = |url: &str| transport.send(Request::get(url));
+
+        assert_eq!(
+            send("http://localhost/shows/1").await.unwrap().status,
+            StatusCode::BAD_GATEWAY
+        );
+        assert_eq!(send("/shows/1").await.unwrap().body, "{}");
+        assert_eq!(send("/shows/1").await.unwrap().body, "{}");
+        assert_eq!(
+            send("/shows/2").await.unwrap().status,
+            StatusCode::NOT_FOUND
+        );
+        assert_eq!(transport.requests().len(), 4);
+    }
+}
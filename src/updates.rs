@@ -1,38 +1,116 @@
-use std::collections::HashMap;
-
-/// A list of all shows in the TVmaze database and the timestamp when they were last updated. Updating a direct or indirect child of a show will also mark the show itself as updated. For example; creating, deleting or updating an episode or an episode's gallery item will mark the episode's show as updated. It's possible to filter the resultset to only include shows that have been updated in the past day (24 hours), week, or month.
-pub async fn show_updates() -> Result<HashMap<usize, usize>, reqwest::Error> {
-    Ok(serde_json::from_str(
-        &reqwest::get(format!("https://api.tvmaze.com/updates/shows"))
-            .await?
-            .text()
-            .await?,
-    )
-    .unwrap())
+use std::{collections::HashMap, fmt::Display, hash::Hash};
+
+use chrono::{DateTime, Utc};
+use serde::de::DeserializeOwned;
+
+use crate::{
+    client::with_query,
+    models::{PersonId, ShowId},
+    Error, Result, TvMazeClient,
+};
+
+/// How far back the update endpoints should look.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UpdateWindow {
+    /// Updated in the past 24 hours.
+    Day,
+    /// Updated in the past 7 days.
+    Week,
+    /// Updated in the past 30 days.
+    Month,
+    /// Every entry in the database.
+    All,
 }
 
-/// Like the show updates endpoint, but for people. A person is considered to be updated when any of their attributes are changed, but also when a cast- or crew-credit that involves them is created or deleted.
-pub async fn person_updates() -> Result<HashMap<usize, usize>, reqwest::Error> {
-    Ok(serde_json::from_str(
-        &reqwest::get(format!("https://api.tvmaze.com/updates/people"))
-            .await?
-            .text()
-            .await?,
-    )
-    .unwrap())
+impl Display for UpdateWindow {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            UpdateWindow::Day => "since=day",
+            UpdateWindow::Week => "since=week",
+            UpdateWindow::Month => "since=month",
+            UpdateWindow::All => "",
+        })
+    }
+}
+
+impl TvMazeClient {
+    /// A list of all shows in the TVmaze database and the timestamp when they were last updated. Updating a direct or indirect child of a show will also mark the show itself as updated. For example; creating, deleting or updating an episode or an episode's gallery item will mark the episode's show as updated. It's possible to filter the resultset to only include shows that have been updated in the past day (24 hours), week, or month.
+    pub async fn show_updates(
+        &self,
+        window: UpdateWindow,
+    ) -> Result<HashMap<ShowId, DateTime<Utc>>> {
+        self.updates(&with_query("/updates/shows", window)).await
+    }
+
+    /// Like the show updates endpoint, but for people. A person is considered to be updated when any of their attributes are changed, but also when a cast- or crew-credit that involves them is created or deleted.
+    pub async fn person_updates(
+        &self,
+        window: UpdateWindow,
+    ) -> Result<HashMap<PersonId, DateTime<Utc>>> {
+        self.updates(&with_query("/updates/people", window)).await
+    }
+
+    async fn updates<K: DeserializeOwned + Eq + Hash + Display>(
+        &self,
+        path: &str,
+    ) -> Result<HashMap<K, DateTime<Utc>>> {
+        let raw: HashMap<K, i64> = self.get(path).await?;
+
+        raw.into_iter()
+            .map(|(id, updated)| match DateTime::from_timestamp(updated, 0) {
+                Some(updated) => Ok((id, updated)),
+                None => Err(Error::Deserialize {
+                    endpoint: path.to_string(),
+                    path: id.to_string(),
+                    source: serde::de::Error::custom("timestamp out of range"),
+                }),
+            })
+            .collect()
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{person_updates, show_updates};
+    use chrono::DateTime;
+
+    use super::UpdateWindow;
+    use crate::{
+        models::{PersonId, ShowId},
+        tests::fixture_client,
+    };
+
+    #[test]
+    fn test_update_window() {
+        assert_eq!(UpdateWindow::Week.to_string(), "since=week");
+        assert_eq!(UpdateWindow::All.to_string(), "");
+    }
 
     #[tokio::test]
     async fn test_show_updates() {
-        show_updates().await.unwrap();
+        let client = fixture_client();
+        let updates = client.show_updates(UpdateWindow::All).await.unwrap();
+        assert_eq!(
+            updates[&ShowId(1)],
+            DateTime::from_timestamp(1704794065, 0).unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_show_updates_since() {
+        let client = fixture_client();
+        client.show_updates(UpdateWindow::Day).await.unwrap();
     }
 
     #[tokio::test]
     async fn test_person_updates() {
-        person_updates().await.unwrap();
+        let client = fixture_client();
+        let updates = client.person_updates(UpdateWindow::All).await.unwrap();
+        assert!(updates.contains_key(&PersonId(1)));
+    }
+
+    #[tokio::test]
+    async fn test_person_updates_since() {
+        let client = fixture_client();
+        client.person_updates(UpdateWindow::Week).await.unwrap();
     }
 }
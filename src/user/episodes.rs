@@ -0,0 +1,230 @@
+use chrono::{DateTime, Utc};
+use serde_json::json;
+
+use crate::{
+    client::with_query,
+    embed::{self, MarkingEmbed},
+    models::{EpisodeId, EpisodeMarking, MarkType, ShowId},
+    Result, TvMazeClient,
+};
+
+impl TvMazeClient {
+    /// All episodes the user marked, optionally only those of one show. Supports embedding the episode.
+    pub async fn episode_markings<E: MarkingEmbed>(
+        &self,
+        show: Option<ShowId>,
+        embed: E,
+    ) -> Result<Vec<EpisodeMarking<E>>> {
+        let mut query = embed::query(&embed);
+        if let Some(show) = show {
+            let filter = format!("show_id={}", show);
+            query = if query.is_empty() {
+                filter
+            } else {
+                format!("{}&{}", filter, query)
+            };
+        }
+        self.user_get(&with_query("/v1/user/episodes", query)).await
+    }
+
+    /// The marking of an episode, returning `None` if it is not marked.
+    pub async fn episode_marking<E: MarkingEmbed>(
+        &self,
+        episode: EpisodeId,
+        embed: E,
+    ) -> Result<Option<EpisodeMarking<E>>> {
+        self.user_get_notfoundable(&with_query(
+            &format!("/v1/user/episodes/{}", episode),
+            embed::query(&embed),
+        ))
+        .await
+    }
+
+    /// Mark an episode, replacing any previous marking.
+    pub async fn mark_episode(
+        &self,
+        episode: EpisodeId,
+        mark_type: MarkType,
+        marked_at: DateTime<Utc>,
+    ) -> Result<EpisodeMarking> {
+        let body = json!({
+            "episode_id": episode.0,
+            "type": mark_type as u8,
+            "marked_at": marked_at.timestamp(),
+        });
+        self.user_put(
+            &format!("/v1/user/episodes/{}", episode),
+            Some(body.to_string()),
+        )
+        .await
+    }
+
+    /// Remove the marking of an episode, returning whether it was marked.
+    pub async fn unmark_episode(&self, episode: EpisodeId) -> Result<bool> {
+        self.user_delete(&format!("/v1/user/episodes/{}", episode))
+            .await
+    }
+
+    /// Mark every episode of a season, specials included, one request per episode. Returns `None` if the season does not exist. This is not atomic: if a request fails, the episodes marked before it stay marked, which [`TvMazeClient::episode_markings`] shows.
+    pub async fn mark_season(
+        &self,
+        season: usize,
+        mark_type: MarkType,
+        marked_at: DateTime<Utc>,
+    ) -> Result<Option<Vec<EpisodeMarking>>> {
+        let Some(episodes) = self.season_episodes(season).await? else {
+            return Ok(None);
+        };
+
+        let mut markings = Vec::with_capacity(episodes.len());
+        for episode in episodes {
+            markings.push(
+                self.mark_episode(EpisodeId(episode.id), mark_type, marked_at)
+                    .await?,
+            );
+        }
+        Ok(Some(markings))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::BTreeMap,
+        sync::{Arc, Mutex},
+    };
+
+    use chrono::{TimeZone, Utc};
+
+    use crate::{
+        embed::Episode,
+        models::{EpisodeId, MarkType, ShowId},
+        user::tests::{self, stand_in, Received},
+        TvMazeClient,
+    };
+
+    /// Marking bodies keyed by episode ID.
+    type Markings = Arc<Mutex<BTreeMap<usize, serde_json::Value>>>;
+
+    /// Render a marking, embedding the episode if requested.
+    fn marking(marking: &serde_json::Value, query: Option<&str>) -> String {
+        let mut marking = marking.clone();
+        if query.is_some_and(|query| query.contains("embed[]=episode")) {
+            let (_, episode) = tests::fixture("/episodes/1");
+            let episode: serde_json::Value = serde_json::from_str(&episode).unwrap();
+            marking["_embedded"] = serde_json::json!({ "episode": episode });
+        }
+        marking.to_string()
+    }
+
+    fn handle(markings: &Markings, req: Received) -> (u16, String) {
+        if req.path.starts_with("/seasons/") {
+            return tests::fixture(&req.path);
+        }
+
+        let (path, query) = req.path_and_query();
+        let mut markings = markings.lock().unwrap();
+
+        match (req.method.as_str(), path.strip_prefix("/v1/user/episodes/")) {
+            ("GET", None) => {
+                // Every stand-in episode belongs to show 1.
+                let show = query.and_then(|query| {
+                    query
+                        .split('&')
+                        .find_map(|pair| pair.strip_prefix("show_id="))
+                });
+                let list: Vec<_> = markings
+                    .values()
+                    .filter(|_| show.is_none_or(|show| show == "1"))
+                    .map(|m| marking(m, query))
+                    .collect();
+                (200, format!("[{}]", list.join(",")))
+            }
+            (method, Some(id)) => {
+                let id: usize = id.parse().unwrap();
+                match method {
+                    "GET" if markings.contains_key(&id) => (200, marking(&markings[&id], query)),
+                    "PUT" => {
+                        let body: serde_json::Value = serde_json::from_str(&req.body).unwrap();
+                        assert_eq!(body["episode_id"], id);
+                        markings.insert(id, body.clone());
+                        (200, body.to_string())
+                    }
+                    "DELETE" if markings.remove(&id).is_some() => (204, String::new()),
+                    _ => tests::not_found(),
+                }
+            }
+            _ => tests::not_found(),
+        }
+    }
+
+    async fn client() -> TvMazeClient {
+        let markings = Markings::default();
+        stand_in(move |req| handle(&markings, req)).await
+    }
+
+    #[tokio::test]
+    async fn test_mark_episode() {
+        let client = client().await;
+        let marked_at = Utc.with_ymd_and_hms(2013, 6, 25, 2, 0, 0).unwrap();
+
+        let marking = client
+            .mark_episode(EpisodeId(1), MarkType::Acquired, marked_at)
+            .await
+            .unwrap();
+        assert_eq!(marking.episode_id, EpisodeId(1));
+        assert_eq!(marking.r#type, MarkType::Acquired);
+        assert_eq!(marking.marked_at, marked_at);
+
+        let marking = client
+            .mark_episode(EpisodeId(1), MarkType::Watched, marked_at)
+            .await
+            .unwrap();
+        assert_eq!(marking.r#type, MarkType::Watched);
+
+        let marking = client
+            .episode_marking(EpisodeId(1), Episode)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(marking._embedded.name, "Pilot");
+        assert_eq!(
+            client
+                .episode_markings(Some(ShowId(1)), ())
+                .await
+                .unwrap()
+                .len(),
+            1
+        );
+        assert!(client
+            .episode_markings(Some(ShowId(2)), ())
+            .await
+            .unwrap()
+            .is_empty());
+
+        assert!(client.unmark_episode(EpisodeId(1)).await.unwrap());
+        assert!(!client.unmark_episode(EpisodeId(1)).await.unwrap());
+        assert!(client
+            .episode_marking(EpisodeId(1), ())
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_mark_season() {
+        let client = client().await;
+        let marked_at = Utc.with_ymd_and_hms(2013, 9, 17, 2, 0, 0).unwrap();
+
+        let markings = client
+            .mark_season(1, MarkType::Skipped, marked_at)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(markings.len(), 13);
+        assert!(markings.iter().all(|m| m.r#type == MarkType::Skipped));
+
+        let listed = client.episode_markings(None, ()).await.unwrap();
+        assert_eq!(listed.len(), 13);
+    }
+}
@@ -0,0 +1,342 @@
+use crate::{
+    client::with_query,
+    embed::{
+        self, FollowedNetworkEmbed, FollowedPersonEmbed, FollowedShowEmbed, FollowedWebChannelEmbed,
+    },
+    models::{
+        FollowedNetwork, FollowedPerson, FollowedShow, FollowedWebChannel, NetworkId, PersonId,
+        ShowId, WebChannelId,
+    },
+    Result, TvMazeClient,
+};
+
+impl TvMazeClient {
+    /// All shows the user follows. Supports embedding the show.
+    pub async fn followed_shows<E: FollowedShowEmbed>(
+        &self,
+        embed: E,
+    ) -> Result<Vec<FollowedShow<E>>> {
+        self.user_get(&with_query("/v1/user/follows/shows", embed::query(&embed)))
+            .await
+    }
+
+    /// Check whether the user follows a show, returning `None` if not.
+    pub async fn followed_show<E: FollowedShowEmbed>(
+        &self,
+        show: ShowId,
+        embed: E,
+    ) -> Result<Option<FollowedShow<E>>> {
+        self.user_get_notfoundable(&with_query(
+            &format!("/v1/user/follows/shows/{}", show),
+            embed::query(&embed),
+        ))
+        .await
+    }
+
+    /// Follow a show. Following a show twice is not an error.
+    pub async fn follow_show(&self, show: ShowId) -> Result<FollowedShow> {
+        self.user_put(&format!("/v1/user/follows/shows/{}", show), None)
+            .await
+    }
+
+    /// Unfollow a show, returning whether it was followed.
+    pub async fn unfollow_show(&self, show: ShowId) -> Result<bool> {
+        self.user_delete(&format!("/v1/user/follows/shows/{}", show))
+            .await
+    }
+
+    /// All people the user follows. Supports embedding the person.
+    pub async fn followed_people<E: FollowedPersonEmbed>(
+        &self,
+        embed: E,
+    ) -> Result<Vec<FollowedPerson<E>>> {
+        self.user_get(&with_query("/v1/user/follows/people", embed::query(&embed)))
+            .await
+    }
+
+    /// Check whether the user follows a person, returning `None` if not.
+    pub async fn followed_person<E: FollowedPersonEmbed>(
+        &self,
+        person: PersonId,
+        embed: E,
+    ) -> Result<Option<FollowedPerson<E>>> {
+        self.user_get_notfoundable(&with_query(
+            &format!("/v1/user/follows/people/{}", person),
+            embed::query(&embed),
+        ))
+        .await
+    }
+
+    /// Follow a person.
+    pub async fn follow_person(&self, person: PersonId) -> Result<FollowedPerson> {
+        self.user_put(&format!("/v1/user/follows/people/{}", person), None)
+            .await
+    }
+
+    /// Unfollow a person, returning whether they were followed.
+    pub async fn unfollow_person(&self, person: PersonId) -> Result<bool> {
+        self.user_delete(&format!("/v1/user/follows/people/{}", person))
+            .await
+    }
+
+    /// All networks the user follows. Supports embedding the network.
+    pub async fn followed_networks<E: FollowedNetworkEmbed>(
+        &self,
+        embed: E,
+    ) -> Result<Vec<FollowedNetwork<E>>> {
+        self.user_get(&with_query(
+            "/v1/user/follows/networks",
+            embed::query(&embed),
+        ))
+        .await
+    }
+
+    /// Check whether the user follows a network, returning `None` if not.
+    pub async fn followed_network<E: FollowedNetworkEmbed>(
+        &self,
+        network: NetworkId,
+        embed: E,
+    ) -> Result<Option<FollowedNetwork<E>>> {
+        self.user_get_notfoundable(&with_query(
+            &format!("/v1/user/follows/networks/{}", network),
+            embed::query(&embed),
+        ))
+        .await
+    }
+
+    /// Follow a network.
+    pub async fn follow_network(&self, network: NetworkId) -> Result<FollowedNetwork> {
+        self.user_put(&format!("/v1/user/follows/networks/{}", network), None)
+            .await
+    }
+
+    /// Unfollow a network, returning whether it was followed.
+    pub async fn unfollow_network(&self, network: NetworkId) -> Result<bool> {
+        self.user_delete(&format!("/v1/user/follows/networks/{}", network))
+            .await
+    }
+
+    /// All web channels the user follows. Supports embedding the web channel.
+    pub async fn followed_web_channels<E: FollowedWebChannelEmbed>(
+        &self,
+        embed: E,
+    ) -> Result<Vec<FollowedWebChannel<E>>> {
+        self.user_get(&with_query(
+            "/v1/user/follows/webchannels",
+            embed::query(&embed),
+        ))
+        .await
+    }
+
+    /// Check whether the user follows a web channel, returning `None` if not.
+    pub async fn followed_web_channel<E: FollowedWebChannelEmbed>(
+        &self,
+        web_channel: WebChannelId,
+        embed: E,
+    ) -> Result<Option<FollowedWebChannel<E>>> {
+        self.user_get_notfoundable(&with_query(
+            &format!("/v1/user/follows/webchannels/{}", web_channel),
+            embed::query(&embed),
+        ))
+        .await
+    }
+
+    /// Follow a web channel.
+    pub async fn follow_web_channel(
+        &self,
+        web_channel: WebChannelId,
+    ) -> Result<FollowedWebChannel> {
+        self.user_put(
+            &format!("/v1/user/follows/webchannels/{}", web_channel),
+            None,
+        )
+        .await
+    }
+
+    /// Unfollow a web channel, returning whether it was followed.
+    pub async fn unfollow_web_channel(&self, web_channel: WebChannelId) -> Result<bool> {
+        self.user_delete(&format!("/v1/user/follows/webchannels/{}", web_channel))
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::BTreeSet,
+        sync::{Arc, Mutex},
+    };
+
+    use reqwest::StatusCode;
+
+    use crate::{
+        embed::{Network, Show},
+        models::{NetworkId, PersonId, ShowId, WebChannelId},
+        user::tests::{self, stand_in, Received},
+        Error, TvMazeClient,
+    };
+
+    /// Follows of every kind, keyed by the kind's path segment.
+    type Follows = Arc<Mutex<BTreeSet<(String, usize)>>>;
+
+    fn id_field(kind: &str) -> &'static str {
+        match kind {
+            "shows" => "show_id",
+            "people" => "person_id",
+            "networks" => "network_id",
+            _ => "webchannel_id",
+        }
+    }
+
+    /// Render a follow, embedding the followed resource from the fixtures if requested.
+    fn follow(kind: &str, id: usize, query: Option<&str>) -> String {
+        let embedded = match (kind, query) {
+            ("shows", Some(query)) if query.contains("show") => {
+                let (_, show) = tests::fixture("/shows/1");
+                format!(r#", "_embedded": {{"show": {}}}"#, show)
+            }
+            ("networks", Some(query)) if query.contains("network") => {
+                r#", "_embedded": {"network": {"id": 2, "name": "CBS", "country": null, "officialSite": null}}"#
+                    .to_string()
+            }
+            _ => String::new(),
+        };
+        format!(r#"{{"{}": {}{}}}"#, id_field(kind), id, embedded)
+    }
+
+    fn handle(follows: &Follows, req: Received) -> (u16, String) {
+        let (path, query) = req.path_and_query();
+        let segments: Vec<_> = path
+            .trim_start_matches("/v1/user/follows/")
+            .split('/')
+            .collect();
+        let mut follows = follows.lock().unwrap();
+
+        match (req.method.as_str(), segments.as_slice()) {
+            ("GET", [kind]) => {
+                let list: Vec<_> = follows
+                    .iter()
+                    .filter(|(k, _)| k == kind)
+                    .map(|(_, id)| follow(kind, *id, query))
+                    .collect();
+                (200, format!("[{}]", list.join(",")))
+            }
+            (method, [kind, id]) => {
+                let key = (kind.to_string(), id.parse().unwrap());
+                match method {
+                    "GET" if follows.contains(&key) => (200, follow(kind, key.1, query)),
+                    "PUT" => {
+                        follows.insert(key.clone());
+                        (200, follow(kind, key.1, None))
+                    }
+                    "DELETE" if follows.remove(&key) => (204, String::new()),
+                    _ => tests::not_found(),
+                }
+            }
+            _ => tests::not_found(),
+        }
+    }
+
+    async fn client() -> TvMazeClient {
+        let follows = Follows::default();
+        stand_in(move |req| handle(&follows, req)).await
+    }
+
+    #[tokio::test]
+    async fn test_follow_shows() {
+        let client = client().await;
+        assert!(client.followed_shows(()).await.unwrap().is_empty());
+
+        assert_eq!(
+            client.follow_show(ShowId(1)).await.unwrap().show_id,
+            ShowId(1)
+        );
+        assert_eq!(client.followed_shows(()).await.unwrap().len(), 1);
+
+        let followed = client
+            .followed_show(ShowId(1), Show)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(followed._embedded.name, "Under the Dome");
+        let followed = client.followed_shows(Show).await.unwrap();
+        assert_eq!(followed[0]._embedded.id, 1);
+
+        assert!(client.unfollow_show(ShowId(1)).await.unwrap());
+        assert!(!client.unfollow_show(ShowId(1)).await.unwrap());
+        assert!(client.followed_show(ShowId(1), ()).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_follow_people() {
+        let client = client().await;
+        client.follow_person(PersonId(1)).await.unwrap();
+        client.follow_person(PersonId(2)).await.unwrap();
+
+        let followed = client.followed_people(()).await.unwrap();
+        assert_eq!(
+            followed.iter().map(|f| f.person_id).collect::<Vec<_>>(),
+            [PersonId(1), PersonId(2)]
+        );
+        assert!(client
+            .followed_person(PersonId(3), ())
+            .await
+            .unwrap()
+            .is_none());
+        assert!(client.unfollow_person(PersonId(2)).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_follow_networks_and_web_channels() {
+        let client = client().await;
+        client.follow_network(NetworkId(2)).await.unwrap();
+        client.follow_web_channel(WebChannelId(1)).await.unwrap();
+
+        let followed = client
+            .followed_network(NetworkId(2), Network)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(followed.network_id, NetworkId(2));
+        assert_eq!(followed._embedded.name, "CBS");
+        assert!(client
+            .followed_network(NetworkId(1), ())
+            .await
+            .unwrap()
+            .is_none());
+
+        let followed = client.followed_web_channels(()).await.unwrap();
+        assert_eq!(followed[0].web_channel_id, WebChannelId(1));
+        assert!(client.unfollow_web_channel(WebChannelId(1)).await.unwrap());
+        assert!(client.unfollow_network(NetworkId(2)).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_follows_unauthorized() {
+        let follows = Follows::default();
+        let client = stand_in(move |req| handle(&follows, req)).await;
+        let anonymous = TvMazeClient::builder()
+            .base_url(client.base_url())
+            .rate_limit(None)
+            .build()
+            .unwrap();
+        assert!(matches!(
+            anonymous.followed_shows(()).await,
+            Err(Error::InvalidConfig(_))
+        ));
+
+        let wrong = TvMazeClient::builder()
+            .base_url(client.base_url())
+            .credentials("user", "wrong")
+            .rate_limit(None)
+            .build()
+            .unwrap();
+        assert!(matches!(
+            wrong.follow_show(ShowId(1)).await,
+            Err(Error::Status {
+                status: StatusCode::UNAUTHORIZED,
+                ..
+            })
+        ));
+    }
+}
@@ -0,0 +1,187 @@
+//! Endpoints of the premium user API, authenticated with the credentials given to [`crate::TvMazeClientBuilder::credentials`].
+
+use reqwest::{Method, StatusCode};
+use serde::de::DeserializeOwned;
+
+use crate::{client::parse, Error, Result, TvMazeClient};
+
+mod episodes;
+mod follows;
+mod tags;
+mod votes;
+
+impl TvMazeClient {
+    pub(crate) async fn user_get<T: DeserializeOwned>(&self, path: &str) -> Result<T> {
+        match self.user_get_notfoundable(path).await? {
+            Some(res) => Ok(res),
+            None => Err(Error::Status {
+                status: StatusCode::NOT_FOUND,
+                body: String::new(),
+            }),
+        }
+    }
+
+    pub(crate) async fn user_get_notfoundable<T: DeserializeOwned>(
+        &self,
+        path: &str,
+    ) -> Result<Option<T>> {
+        match self.send_user(Method::GET, path, None).await? {
+            Some(body) => parse(path, &body).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// `PUT` to `path`, parsing the response.
+    pub(crate) async fn user_put<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        body: Option<String>,
+    ) -> Result<T> {
+        self.user_write(Method::PUT, path, body).await
+    }
+
+    /// Send `body` to `path` with `method`, parsing the response. A 404 is an error.
+    pub(crate) async fn user_write<T: DeserializeOwned>(
+        &self,
+        method: Method,
+        path: &str,
+        body: Option<String>,
+    ) -> Result<T> {
+        match self.send_user(method, path, body).await? {
+            Some(body) => parse(path, &body),
+            None => Err(Error::Status {
+                status: StatusCode::NOT_FOUND,
+                body: String::new(),
+            }),
+        }
+    }
+
+    /// `DELETE` `path`, returning whether there was anything to delete.
+    pub(crate) async fn user_delete(&self, path: &str) -> Result<bool> {
+        Ok(self.send_user(Method::DELETE, path, None).await?.is_some())
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use std::sync::Arc;
+
+    use tokio::{
+        io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader},
+        net::{TcpListener, TcpStream},
+    };
+
+    use crate::{tests::payload, RetryPolicy, TvMazeClient};
+
+    /// Basic auth header of the credentials used by [`stand_in`] clients.
+    const AUTHORIZATION: &str = "Basic dXNlcjprZXk=";
+
+    /// A request received by the stand-in server.
+    #[derive(Debug)]
+    pub struct Received {
+        pub method: String,
+        pub path: String,
+        pub body: String,
+    }
+
+    impl Received {
+        /// The path without the query string, and the query string if there is one.
+        pub fn path_and_query(&self) -> (&str, Option<&str>) {
+            match self.path.split_once('?') {
+                Some((path, query)) => (path, Some(query)),
+                None => (self.path.as_str(), None),
+            }
+        }
+    }
+
+    /// The response of the API for a resource that does not exist.
+    pub fn not_found() -> (u16, String) {
+        (404, r#"{"name": "Not Found"}"#.to_string())
+    }
+
+    /// Status and body of the fixture for a public API request, or a 404 if there is none.
+    pub fn fixture(path: &str) -> (u16, String) {
+        match payload(path) {
+            Some(fixture) => (
+                fixture["status"].as_u64().unwrap() as u16,
+                fixture["body"].to_string(),
+            ),
+            None => not_found(),
+        }
+    }
+
+    /// Start a minimal HTTP server on localhost standing in for the user API, answering user API requests without the credentials with a 401 and every other request with the status and body returned by `handler`. Returns a client with credentials pointed at it.
+    pub async fn stand_in(
+        handler: impl Fn(Received) -> (u16, String) + Send + Sync + 'static,
+    ) -> TvMazeClient {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let handler = Arc::new(handler);
+
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                let handler = handler.clone();
+                tokio::spawn(async move { serve(stream, &*handler).await });
+            }
+        });
+
+        TvMazeClient::builder()
+            .base_url(format!("http://{}", addr))
+            .credentials("user", "key")
+            .rate_limit(None)
+            .retry_policy(RetryPolicy::NONE)
+            .build()
+            .unwrap()
+    }
+
+    /// Answer a single request on `stream`, then close it.
+    async fn serve(stream: TcpStream, handler: &impl Fn(Received) -> (u16, String)) {
+        let mut reader = BufReader::new(stream);
+
+        let mut line = String::new();
+        reader.read_line(&mut line).await.unwrap();
+        let mut request_line = line.split_whitespace();
+        let method = request_line.next().unwrap_or_default().to_string();
+        let path = request_line.next().unwrap_or_default().to_string();
+
+        let mut authorization = None;
+        let mut content_length = 0;
+        loop {
+            line.clear();
+            reader.read_line(&mut line).await.unwrap();
+            let Some((name, value)) = line.trim_end().split_once(':') else {
+                break;
+            };
+            if name.eq_ignore_ascii_case("authorization") {
+                authorization = Some(value.trim().to_string());
+            } else if name.eq_ignore_ascii_case("content-length") {
+                content_length = value.trim().parse().unwrap();
+            }
+        }
+
+        let mut body = vec![0; content_length];
+        reader.read_exact(&mut body).await.unwrap();
+
+        let (status, body) =
+            if path.starts_with("/v1/user/") && authorization.as_deref() != Some(AUTHORIZATION) {
+                (401, r#"{"name": "Unauthorized"}"#.to_string())
+            } else {
+                handler(Received {
+                    method,
+                    path,
+                    body: String::from_utf8(body).unwrap(),
+                })
+            };
+        let response = format!(
+            "HTTP/1.1 {} Stand-in\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            status,
+            body.len(),
+            body
+        );
+        reader
+            .into_inner()
+            .write_all(response.as_bytes())
+            .await
+            .unwrap();
+    }
+}
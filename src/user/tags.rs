@@ -0,0 +1,226 @@
+use reqwest::Method;
+use serde_json::json;
+
+use crate::{
+    embed::ShowEmbed,
+    models::{Show, ShowId, Tag, TagId, TaggedShow},
+    Result, TvMazeClient,
+};
+
+impl TvMazeClient {
+    /// All tags of the user.
+    pub async fn tags(&self) -> Result<Vec<Tag>> {
+        self.user_get("/v1/user/tags").await
+    }
+
+    /// Create a tag.
+    pub async fn create_tag(&self, name: &str) -> Result<Tag> {
+        let body = json!({ "name": name });
+        self.user_write(Method::POST, "/v1/user/tags", Some(body.to_string()))
+            .await
+    }
+
+    /// Rename a tag.
+    pub async fn rename_tag(&self, tag: TagId, name: &str) -> Result<Tag> {
+        let body = json!({ "name": name });
+        self.user_write(
+            Method::PATCH,
+            &format!("/v1/user/tags/{}", tag),
+            Some(body.to_string()),
+        )
+        .await
+    }
+
+    /// Delete a tag, returning whether it existed.
+    pub async fn delete_tag(&self, tag: TagId) -> Result<bool> {
+        self.user_delete(&format!("/v1/user/tags/{}", tag)).await
+    }
+
+    /// The shows assigned to a tag, returning `None` if the tag does not exist.
+    pub async fn tag_shows(&self, tag: TagId) -> Result<Option<Vec<TaggedShow>>> {
+        self.user_get_notfoundable(&format!("/v1/user/tags/{}/shows", tag))
+            .await
+    }
+
+    /// The shows assigned to a tag, each fetched with [`TvMazeClient::show`]. Returns `None` if the tag does not exist. Tagged shows that no longer exist on TVmaze are left out.
+    pub async fn tagged_shows<E: ShowEmbed + Clone>(
+        &self,
+        tag: TagId,
+        embed: E,
+    ) -> Result<Option<Vec<Show<E>>>> {
+        let Some(tagged) = self.tag_shows(tag).await? else {
+            return Ok(None);
+        };
+
+        let mut shows = Vec::with_capacity(tagged.len());
+        for tagged in tagged {
+            if let Some(show) = self.show(tagged.show_id.0, embed.clone()).await? {
+                shows.push(show);
+            }
+        }
+        Ok(Some(shows))
+    }
+
+    /// Assign a show to a tag.
+    pub async fn tag_show(&self, tag: TagId, show: ShowId) -> Result<TaggedShow> {
+        self.user_put(&format!("/v1/user/tags/{}/shows/{}", tag, show), None)
+            .await
+    }
+
+    /// Remove a show from a tag, returning whether it was assigned.
+    pub async fn untag_show(&self, tag: TagId, show: ShowId) -> Result<bool> {
+        self.user_delete(&format!("/v1/user/tags/{}/shows/{}", tag, show))
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::{BTreeMap, BTreeSet},
+        sync::{Arc, Mutex},
+    };
+
+    use crate::{
+        models::{ShowId, TagId},
+        tests::FULL_EMBED,
+        user::tests::{self, stand_in, Received},
+        TvMazeClient,
+    };
+
+    /// Tag names and their shows, keyed by tag ID.
+    type Tags = Arc<Mutex<BTreeMap<usize, (String, BTreeSet<usize>)>>>;
+
+    fn tag(id: usize, name: &str) -> String {
+        serde_json::json!({ "id": id, "name": name }).to_string()
+    }
+
+    fn name(body: &str) -> String {
+        let body: serde_json::Value = serde_json::from_str(body).unwrap();
+        body["name"].as_str().unwrap().to_string()
+    }
+
+    fn handle(tags: &Tags, req: Received) -> (u16, String) {
+        if req.path.starts_with("/shows/") {
+            return tests::fixture(&req.path);
+        }
+
+        let segments: Vec<_> = req
+            .path
+            .trim_start_matches("/v1/user/tags")
+            .split('/')
+            .skip(1)
+            .collect();
+        let mut tags = tags.lock().unwrap();
+
+        match (req.method.as_str(), segments.as_slice()) {
+            ("GET", []) => {
+                let list: Vec<_> = tags.iter().map(|(id, (name, _))| tag(*id, name)).collect();
+                (200, format!("[{}]", list.join(",")))
+            }
+            ("POST", []) => {
+                let id = tags.last_key_value().map_or(1, |(id, _)| id + 1);
+                let name = name(&req.body);
+                let rendered = tag(id, &name);
+                tags.insert(id, (name, BTreeSet::new()));
+                (200, rendered)
+            }
+            (method, [id, rest @ ..]) => {
+                let id: usize = id.parse().unwrap();
+                let Some((tag_name, shows)) = tags.get_mut(&id) else {
+                    return tests::not_found();
+                };
+                match (method, rest) {
+                    ("PATCH", []) => {
+                        *tag_name = name(&req.body);
+                        (200, tag(id, tag_name))
+                    }
+                    ("DELETE", []) => {
+                        tags.remove(&id);
+                        (204, String::new())
+                    }
+                    ("GET", ["shows"]) => {
+                        let list: Vec<_> = shows
+                            .iter()
+                            .map(|show| format!(r#"{{"show_id": {}}}"#, show))
+                            .collect();
+                        (200, format!("[{}]", list.join(",")))
+                    }
+                    ("PUT", ["shows", show]) => {
+                        shows.insert(show.parse().unwrap());
+                        (200, format!(r#"{{"show_id": {}}}"#, show))
+                    }
+                    ("DELETE", ["shows", show]) if shows.remove(&show.parse().unwrap()) => {
+                        (204, String::new())
+                    }
+                    _ => tests::not_found(),
+                }
+            }
+            _ => tests::not_found(),
+        }
+    }
+
+    async fn client() -> TvMazeClient {
+        let tags = Tags::default();
+        stand_in(move |req| handle(&tags, req)).await
+    }
+
+    #[tokio::test]
+    async fn test_tags() {
+        let client = client().await;
+        assert!(client.tags().await.unwrap().is_empty());
+
+        let created = client.create_tag("Watching").await.unwrap();
+        assert_eq!((created.id, created.name.as_str()), (TagId(1), "Watching"));
+        client.create_tag("Backlog").await.unwrap();
+
+        let renamed = client
+            .rename_tag(TagId(1), "Currently watching")
+            .await
+            .unwrap();
+        assert_eq!(renamed.name, "Currently watching");
+        assert_eq!(
+            client
+                .tags()
+                .await
+                .unwrap()
+                .iter()
+                .map(|tag| tag.name.as_str())
+                .collect::<Vec<_>>(),
+            ["Currently watching", "Backlog"]
+        );
+
+        assert!(client.delete_tag(TagId(2)).await.unwrap());
+        assert!(!client.delete_tag(TagId(2)).await.unwrap());
+        assert_eq!(client.tags().await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_tagged_shows() {
+        let client = client().await;
+        let tag = client.create_tag("Favourites").await.unwrap().id;
+        assert!(client
+            .tagged_shows(TagId(tag.0 + 1), ())
+            .await
+            .unwrap()
+            .is_none());
+
+        assert_eq!(
+            client.tag_show(tag, ShowId(1371)).await.unwrap().show_id,
+            ShowId(1371)
+        );
+        // Shows removed from TVmaze are skipped.
+        client.tag_show(tag, ShowId(999999)).await.unwrap();
+        let shows = client.tagged_shows(tag, FULL_EMBED).await.unwrap().unwrap();
+        assert_eq!(shows.len(), 1);
+        assert_eq!(shows[0].name, "Westworld");
+        assert!(!shows[0]._embedded.0.is_empty());
+
+        assert!(client.untag_show(tag, ShowId(1371)).await.unwrap());
+        assert!(!client.untag_show(tag, ShowId(1371)).await.unwrap());
+        assert!(client.untag_show(tag, ShowId(999999)).await.unwrap());
+        client.tag_show(tag, ShowId(1)).await.unwrap();
+        let shows = client.tagged_shows(tag, ()).await.unwrap().unwrap();
+        assert_eq!(shows[0].name, "Under the Dome");
+    }
+}
@@ -0,0 +1,219 @@
+use serde_json::json;
+
+use crate::{
+    client::with_query,
+    embed::{self, EpisodeVoteEmbed, ShowVoteEmbed},
+    models::{EpisodeId, EpisodeVote, ShowId, ShowVote},
+    Error, Result, TvMazeClient,
+};
+
+impl TvMazeClient {
+    /// All shows the user voted on. Supports embedding the show.
+    pub async fn show_votes<E: ShowVoteEmbed>(&self, embed: E) -> Result<Vec<ShowVote<E>>> {
+        self.user_get(&with_query("/v1/user/votes/shows", embed::query(&embed)))
+            .await
+    }
+
+    /// The user's vote on a show, returning `None` if they did not vote on it.
+    pub async fn show_vote<E: ShowVoteEmbed>(
+        &self,
+        show: ShowId,
+        embed: E,
+    ) -> Result<Option<ShowVote<E>>> {
+        self.user_get_notfoundable(&with_query(
+            &format!("/v1/user/votes/shows/{}", show),
+            embed::query(&embed),
+        ))
+        .await
+    }
+
+    /// Vote on a show from 1 to 10, replacing any previous vote. Other values fail with [`Error::InvalidArgument`].
+    pub async fn vote_show(&self, show: ShowId, vote: u8) -> Result<ShowVote> {
+        check_vote(vote)?;
+        let body = json!({ "show_id": show.0, "vote": vote });
+        self.user_put(
+            &format!("/v1/user/votes/shows/{}", show),
+            Some(body.to_string()),
+        )
+        .await
+    }
+
+    /// Remove the user's vote on a show, returning whether there was one.
+    pub async fn unvote_show(&self, show: ShowId) -> Result<bool> {
+        self.user_delete(&format!("/v1/user/votes/shows/{}", show))
+            .await
+    }
+
+    /// All episodes the user voted on. Supports embedding the episode.
+    pub async fn episode_votes<E: EpisodeVoteEmbed>(
+        &self,
+        embed: E,
+    ) -> Result<Vec<EpisodeVote<E>>> {
+        self.user_get(&with_query("/v1/user/votes/episodes", embed::query(&embed)))
+            .await
+    }
+
+    /// The user's vote on an episode, returning `None` if they did not vote on it.
+    pub async fn episode_vote<E: EpisodeVoteEmbed>(
+        &self,
+        episode: EpisodeId,
+        embed: E,
+    ) -> Result<Option<EpisodeVote<E>>> {
+        self.user_get_notfoundable(&with_query(
+            &format!("/v1/user/votes/episodes/{}", episode),
+            embed::query(&embed),
+        ))
+        .await
+    }
+
+    /// Vote on an episode from 1 to 10, replacing any previous vote. Other values fail with [`Error::InvalidArgument`].
+    pub async fn vote_episode(&self, episode: EpisodeId, vote: u8) -> Result<EpisodeVote> {
+        check_vote(vote)?;
+        let body = json!({ "episode_id": episode.0, "vote": vote });
+        self.user_put(
+            &format!("/v1/user/votes/episodes/{}", episode),
+            Some(body.to_string()),
+        )
+        .await
+    }
+
+    /// Remove the user's vote on an episode, returning whether there was one.
+    pub async fn unvote_episode(&self, episode: EpisodeId) -> Result<bool> {
+        self.user_delete(&format!("/v1/user/votes/episodes/{}", episode))
+            .await
+    }
+}
+
+fn check_vote(vote: u8) -> Result<()> {
+    if (1..=10).contains(&vote) {
+        Ok(())
+    } else {
+        Err(Error::InvalidArgument("votes range from 1 to 10"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::BTreeMap,
+        sync::{Arc, Mutex},
+    };
+
+    use crate::{
+        embed::Show,
+        models::{EpisodeId, ShowId},
+        user::tests::{self, stand_in, Received},
+        Error, TvMazeClient,
+    };
+
+    /// Votes keyed by kind and ID.
+    type Votes = Arc<Mutex<BTreeMap<(String, usize), u64>>>;
+
+    /// Render a vote, embedding the show from the fixtures if requested.
+    fn vote(kind: &str, id: usize, vote: u64, query: Option<&str>) -> String {
+        let id_field = if kind == "shows" {
+            "show_id"
+        } else {
+            "episode_id"
+        };
+        let mut rendered =
+            serde_json::json!({ id_field: id, "voted_at": 1372125600, "vote": vote });
+        if kind == "shows" && query.is_some_and(|query| query.contains("embed[]=show")) {
+            let (_, show) = tests::fixture("/shows/1");
+            let show: serde_json::Value = serde_json::from_str(&show).unwrap();
+            rendered["_embedded"] = serde_json::json!({ "show": show });
+        }
+        rendered.to_string()
+    }
+
+    fn handle(votes: &Votes, req: Received) -> (u16, String) {
+        let (path, query) = req.path_and_query();
+        let segments: Vec<_> = path
+            .trim_start_matches("/v1/user/votes/")
+            .split('/')
+            .collect();
+        let mut votes = votes.lock().unwrap();
+
+        match (req.method.as_str(), segments.as_slice()) {
+            ("GET", [kind]) => {
+                let list: Vec<_> = votes
+                    .iter()
+                    .filter(|((k, _), _)| k == kind)
+                    .map(|((_, id), v)| vote(kind, *id, *v, query))
+                    .collect();
+                (200, format!("[{}]", list.join(",")))
+            }
+            (method, [kind, id]) => {
+                let key = (kind.to_string(), id.parse().unwrap());
+                match method {
+                    "GET" if votes.contains_key(&key) => {
+                        (200, vote(kind, key.1, votes[&key], query))
+                    }
+                    "PUT" => {
+                        let body: serde_json::Value = serde_json::from_str(&req.body).unwrap();
+                        let value = body["vote"].as_u64().unwrap();
+                        votes.insert(key.clone(), value);
+                        (200, vote(kind, key.1, value, None))
+                    }
+                    "DELETE" if votes.remove(&key).is_some() => (204, String::new()),
+                    _ => tests::not_found(),
+                }
+            }
+            _ => tests::not_found(),
+        }
+    }
+
+    async fn client() -> TvMazeClient {
+        let votes = Votes::default();
+        stand_in(move |req| handle(&votes, req)).await
+    }
+
+    #[tokio::test]
+    async fn test_show_votes() {
+        let client = client().await;
+        let voted = client.vote_show(ShowId(1), 8).await.unwrap();
+        assert_eq!((voted.show_id, voted.vote), (ShowId(1), 8));
+        assert_eq!(client.vote_show(ShowId(1), 9).await.unwrap().vote, 9);
+
+        let voted = client.show_vote(ShowId(1), Show).await.unwrap().unwrap();
+        assert_eq!(voted._embedded.name, "Under the Dome");
+        assert_eq!(voted.voted_at.timestamp(), 1372125600);
+        assert_eq!(client.show_votes(()).await.unwrap().len(), 1);
+
+        assert!(matches!(
+            client.vote_show(ShowId(1), 11).await,
+            Err(Error::InvalidArgument(_))
+        ));
+        assert!(matches!(
+            client.vote_episode(EpisodeId(1), 0).await,
+            Err(Error::InvalidArgument(_))
+        ));
+
+        assert!(client.unvote_show(ShowId(1)).await.unwrap());
+        assert!(!client.unvote_show(ShowId(1)).await.unwrap());
+        assert!(client.show_vote(ShowId(1), ()).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_episode_votes() {
+        let client = client().await;
+        client.vote_episode(EpisodeId(1), 7).await.unwrap();
+        client.vote_episode(EpisodeId(2), 10).await.unwrap();
+
+        let votes = client.episode_votes(()).await.unwrap();
+        assert_eq!(
+            votes
+                .iter()
+                .map(|v| (v.episode_id, v.vote))
+                .collect::<Vec<_>>(),
+            [(EpisodeId(1), 7), (EpisodeId(2), 10)]
+        );
+        assert!(client
+            .episode_vote(EpisodeId(3), ())
+            .await
+            .unwrap()
+            .is_none());
+        assert!(client.unvote_episode(EpisodeId(2)).await.unwrap());
+        assert!(client.show_votes(()).await.unwrap().is_empty());
+    }
+}